@@ -0,0 +1,33 @@
+use crate::router::{Route, use_router};
+use freya::prelude::*;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct AuthorLinkProps {
+  /// The author's username; without one the children are not clickable.
+  pub name: Option<String>,
+  pub children: Element,
+}
+
+/// Wraps an author name so that clicking it opens the user's profile.
+#[component]
+pub fn AuthorLink(props: AuthorLinkProps) -> Element {
+  let mut router = use_router();
+
+  let Some(name) = props.name else {
+    return props.children;
+  };
+
+  rsx! {
+      CursorArea {
+          icon: CursorIcon::Pointer,
+          rect {
+              onclick: move |e: MouseEvent| {
+                  // Authors are shown inside clickable cards; don't open those as well.
+                  e.stop_propagation();
+                  router.push(Route::User(name.clone()));
+              },
+              {props.children}
+          }
+      }
+  }
+}
@@ -0,0 +1,31 @@
+use crate::theme::Theme;
+use crate::utils::bookmarks::{Bookmarks, SavedItem};
+use freya::prelude::*;
+
+/// A star that saves an item for later, or removes it from the saved items.
+#[component]
+pub fn BookmarkStar(item: SavedItem) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut bookmarks = use_context::<Signal<Bookmarks>>();
+  let is_saved = bookmarks.read().is_saved(item.id());
+
+  rsx! {
+      CursorArea {
+          icon: CursorIcon::Pointer,
+          rect {
+              padding: "0 4",
+              onclick: move |e: MouseEvent| {
+                  // Stars are shown inside clickable cards; don't open those as well.
+                  e.stop_propagation();
+                  bookmarks.write().toggle(item.clone());
+              },
+              label {
+                  font_size: "{theme.size.text_xl}",
+                  color: if is_saved { "{theme.color.accent}" } else { "{theme.color.text_alt}" },
+                  if is_saved { "★" } else { "☆" }
+              }
+          }
+      }
+  }
+}
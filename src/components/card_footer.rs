@@ -1,4 +1,5 @@
 use crate::Story;
+use crate::components::author_link::AuthorLink;
 use crate::components::footer_label::FooterLabel;
 use crate::components::icons::*;
 use crate::components::primitives::Spacer;
@@ -8,6 +9,9 @@ use freya::prelude::*;
 #[derive(Props, PartialEq, Clone)]
 pub struct CardFooterProps {
   pub story: Story,
+  /// Comments added since the story was last opened.
+  #[props(default)]
+  pub new_comments: u32,
 }
 
 #[component]
@@ -17,16 +21,28 @@ pub fn CardFooter(props: CardFooterProps) -> Element {
   const FOOTER_PADDING_TOP: &str = "10";
   const SPACER_WIDTH: &str = "16";
 
-  let mut footer_items: Vec<(Element, String)> = vec![
-    (rsx! { IconScore {} }, format!("{}", story.score.unwrap_or(0))),
-    (rsx! { IconUser {} }, story.by.as_deref().unwrap_or("N/A").to_string()),
+  let author = story.by.as_deref().unwrap_or("N/A").to_string();
+  let mut footer_items: Vec<Element> = vec![
+    rsx! { FooterLabel { icon: rsx! { IconScore {} }, text: format!("{}", story.score.unwrap_or(0)) } },
+    rsx! {
+        AuthorLink {
+            name: story.by.clone(),
+            FooterLabel { icon: rsx! { IconUser {} }, text: author }
+        }
+    },
   ];
 
   if let Some(time) = &story.time {
-    footer_items.push((rsx! { IconTime {} }, format_timestamp(time)));
+    footer_items.push(rsx! { FooterLabel { icon: rsx! { IconTime {} }, text: format_timestamp(time) } });
   }
 
-  footer_items.push((rsx! { IconComments {} }, format!("{}", story.descendants.unwrap_or(0))));
+  footer_items.push(rsx! {
+      FooterLabel { icon: rsx! { IconComments {} }, text: format!("{}", story.descendants.unwrap_or(0)) }
+  });
+
+  if props.new_comments > 0 {
+    footer_items.push(rsx! { FooterLabel { text: format!("+{} new comments", props.new_comments) } });
+  }
 
   rsx! {
       ScrollView {
@@ -42,16 +58,13 @@ pub fn CardFooter(props: CardFooterProps) -> Element {
               height: "auto",
 
               {
-                  footer_items.into_iter().enumerate().map(|(i, (icon, text))| {
+                  footer_items.into_iter().enumerate().map(|(i, item)| {
                       rsx! {
                           Fragment {
                               if i > 0 {
                                   Spacer { width: SPACER_WIDTH }
                               }
-                              FooterLabel {
-                                  icon: Some(icon),
-                                  text: text
-                              }
+                              {item}
                           }
                       }
                   })
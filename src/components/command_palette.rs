@@ -0,0 +1,380 @@
+use crate::models::{Item, Story};
+use crate::theme::Theme;
+use crate::utils::api::{ApiService, StoryListType};
+use crate::utils::fuzzy;
+use crate::utils::keymap::Keymap;
+use crate::utils::keys::KeyAction;
+use freya::prelude::*;
+use log::info;
+use std::sync::Arc;
+
+// How many recently used commands are remembered for ordering.
+const RECENT_LIMIT: usize = 20;
+
+/// What the app is asked to do when a palette command runs.
+#[derive(Clone, PartialEq)]
+pub enum Command {
+  SelectList(StoryListType),
+  NextTab,
+  PreviousTab,
+  ToggleTheme,
+  /// Shows or hides the stories already read in the lists.
+  ToggleHideRead,
+  /// Shows only the stories with a highlighted title in the lists, or all again.
+  ToggleOnlyHighlighted,
+  Refresh,
+  Back,
+  Forward,
+  CloseTab,
+  ShowSaved,
+  ShowHistory,
+  ManageMutes,
+  ManageHighlights,
+  /// Opens the search bar, searching right away for the text if there is one.
+  Search(Option<String>),
+  OpenUser(String),
+  OpenStory(Box<Story>),
+}
+
+#[derive(Clone, PartialEq)]
+enum Run {
+  Command(Command),
+  // The story is fetched by the palette before it asks the app to open it.
+  FetchStory(u32),
+}
+
+#[derive(Clone, PartialEq)]
+struct Entry {
+  // Stable across queries, for the recently used list.
+  id: String,
+  title: String,
+  action: Option<KeyAction>,
+  run: Run,
+}
+
+impl Entry {
+  fn new(id: impl Into<String>, title: impl Into<String>, action: Option<KeyAction>, command: Command) -> Self {
+    Self { id: id.into(), title: title.into(), action, run: Run::Command(command) }
+  }
+}
+
+// The commands that do not depend on what was typed.
+fn fixed_entries() -> Vec<Entry> {
+  let mut entries: Vec<Entry> = StoryListType::ALL
+    .iter()
+    .enumerate()
+    .map(|(index, list_type)| {
+      Entry::new(
+        format!("list_{list_type}"),
+        format!("Show {list_type} stories"),
+        Some(KeyAction::SelectTab(index)),
+        Command::SelectList(*list_type),
+      )
+    })
+    .collect();
+  entries.extend([
+    Entry::new("next_tab", "Next tab", Some(KeyAction::NextTab), Command::NextTab),
+    Entry::new("previous_tab", "Previous tab", Some(KeyAction::PreviousTab), Command::PreviousTab),
+    Entry::new("toggle_theme", "Toggle light/dark theme", Some(KeyAction::ToggleTheme), Command::ToggleTheme),
+    Entry::new("toggle_hide_read", "Hide/show read stories", None, Command::ToggleHideRead),
+    Entry::new(
+      "toggle_only_highlighted",
+      "Show only highlighted stories/all stories",
+      None,
+      Command::ToggleOnlyHighlighted,
+    ),
+    Entry::new("refresh", "Refresh", Some(KeyAction::Refresh), Command::Refresh),
+    Entry::new("back", "Go back", Some(KeyAction::Back), Command::Back),
+    Entry::new("forward", "Go forward", Some(KeyAction::Forward), Command::Forward),
+    Entry::new("close_tab", "Close story tab", Some(KeyAction::CloseTab), Command::CloseTab),
+    Entry::new("show_saved", "Show saved stories", None, Command::ShowSaved),
+    Entry::new("show_history", "Show reading history", None, Command::ShowHistory),
+    Entry::new("manage_mutes", "Manage muted stories, domains and users", None, Command::ManageMutes),
+    Entry::new("manage_highlights", "Manage highlight rules", None, Command::ManageHighlights),
+    Entry::new("search", "Search…", None, Command::Search(None)),
+  ]);
+  entries
+}
+
+// Orders the entries matching `query`: by match quality, then most recently used
+// first. The commands taking the typed text as their argument come last, except
+// that a story id is most likely meant to be opened.
+fn ranked_entries(query: &str, recent: &[String]) -> Vec<Entry> {
+  let query = query.trim();
+  let recency = |entry: &Entry| recent.iter().position(|id| *id == entry.id).unwrap_or(usize::MAX);
+
+  let mut matches: Vec<(i32, Entry)> =
+    fixed_entries().into_iter().filter_map(|entry| Some((fuzzy::score(query, &entry.title)?, entry))).collect();
+  matches.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then_with(|| recency(a).cmp(&recency(b))));
+  let mut entries: Vec<Entry> = matches.into_iter().map(|(_, entry)| entry).collect();
+
+  if query.is_empty() {
+    return entries;
+  }
+  if let Ok(id) = query.trim_start_matches('#').parse::<u32>() {
+    entries.insert(
+      0,
+      Entry { id: "open_story".into(), title: format!("Open story #{id}"), action: None, run: Run::FetchStory(id) },
+    );
+  }
+  if is_username(query) {
+    entries.push(Entry::new("open_user", format!("Go to user {query}"), None, Command::OpenUser(query.to_string())));
+  }
+  entries.push(Entry::new(
+    "search_text",
+    format!("Search for “{query}”"),
+    None,
+    Command::Search(Some(query.to_string())),
+  ));
+  entries
+}
+
+// HN usernames are 2 to 15 letters, digits, dashes and underscores.
+fn is_username(text: &str) -> bool {
+  (2..=15).contains(&text.len()) && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[derive(Clone, PartialEq)]
+enum Status {
+  Idle,
+  Loading(u32),
+  Failed(String),
+}
+
+// --- Main Component ---
+/// A Ctrl+K overlay for running any app command by typing part of its name, with
+/// the key bindings of each command shown next to it.
+#[component]
+pub fn CommandPalette(
+  recent: Signal<Vec<String>>,
+  on_command: EventHandler<Command>,
+  on_close: EventHandler<()>,
+) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let keymap = use_context::<Signal<Keymap>>();
+  let api_service = use_context::<Arc<ApiService>>();
+
+  let mut query = use_signal(String::new);
+  let mut selected = use_signal(|| 0usize);
+  let mut status = use_signal(|| Status::Idle);
+  let entries = use_memo(move || ranked_entries(&query.read(), &recent.read()));
+
+  let run = use_callback(move |entry: Entry| {
+    {
+      let mut recent = recent.write();
+      recent.retain(|id| *id != entry.id);
+      recent.insert(0, entry.id);
+      recent.truncate(RECENT_LIMIT);
+    }
+    match entry.run {
+      Run::Command(command) => {
+        on_command.call(command);
+        on_close.call(());
+      }
+      Run::FetchStory(id) => {
+        let api_service = api_service.clone();
+        status.set(Status::Loading(id));
+        spawn(async move {
+          info!("Opening item {id} from the command palette");
+          match api_service.fetch_item::<Item>(id).await.map(Item::into_story) {
+            Ok(Some(story)) => {
+              on_command.call(Command::OpenStory(Box::new(story)));
+              on_close.call(());
+            }
+            Ok(None) => status.set(Status::Failed(format!("#{id} is not a story, job or poll"))),
+            Err(e) => status.set(Status::Failed(format!("Could not open #{id}: {e}"))),
+          }
+        });
+      }
+    }
+  });
+
+  let onglobalkeydown = move |e: KeyboardEvent| {
+    let count = entries.read().len();
+    match e.key {
+      Key::Escape => on_close.call(()),
+      Key::ArrowDown | Key::ArrowUp if count > 0 => {
+        let step = if e.key == Key::ArrowDown { 1 } else { count - 1 };
+        let next = (*selected.peek() + step) % count;
+        selected.set(next);
+      }
+      Key::Enter => {
+        let entry = entries.read().get(*selected.peek()).cloned();
+        if let Some(entry) = entry {
+          run.call(entry);
+        }
+      }
+      _ => {}
+    }
+  };
+
+  let keymap = keymap.read();
+
+  rsx! {
+      rect {
+          layer: "-2000",
+          // Backdrop; clicking outside the palette closes it.
+          rect {
+              onclick: move |_| on_close.call(()),
+              width: "100v",
+              height: "100v",
+              background: "rgb(0, 0, 0, 120)",
+              position: "global",
+              position_top: "0",
+              position_left: "0",
+          }
+          rect {
+              width: "100v",
+              position: "global",
+              position_top: "80",
+              position_left: "0",
+              cross_align: "center",
+              rect {
+                  width: "520",
+                  padding: "8",
+                  corner_radius: "8",
+                  background: "{theme.color.background_card}",
+                  border: "1 solid {theme.color.border}",
+                  shadow: "0 4 12 0 rgb(0, 0, 0, 60)",
+                  direction: "vertical",
+                  onglobalkeydown,
+                  Input {
+                      width: "100%",
+                      value: query.read().clone(),
+                      placeholder: "Type a command, a story ID or a user name",
+                      auto_focus: true,
+                      onchange: move |value| {
+                          query.set(value);
+                          selected.set(0);
+                          status.set(Status::Idle);
+                      },
+                  }
+                  match status.read().clone() {
+                      Status::Idle => rsx! {},
+                      Status::Loading(id) => rsx! {
+                          label {
+                              margin: "6 4 0 4",
+                              font_size: "{theme.size.text_s}",
+                              color: "{theme.color.text_alt}",
+                              "Opening #{id}…"
+                          }
+                      },
+                      Status::Failed(message) => rsx! {
+                          label {
+                              margin: "6 4 0 4",
+                              font_size: "{theme.size.text_s}",
+                              color: "red",
+                              "{message}"
+                          }
+                      },
+                  }
+                  rect {
+                      width: "100%",
+                      margin: "6 0 0 0",
+                      direction: "vertical",
+                      for (index, entry) in entries.read().iter().cloned().enumerate() {
+                          rect {
+                              key: "{entry.id}",
+                              width: "100%",
+                              padding: "6 8",
+                              corner_radius: "4",
+                              direction: "horizontal",
+                              content: "flex",
+                              cross_align: "center",
+                              background: if index == *selected.read() { "{theme.color.tab_background_hover}" } else { "transparent" },
+                              onmouseenter: move |_| selected.set(index),
+                              onclick: {
+                                  let entry = entry.clone();
+                                  move |_| run.call(entry.clone())
+                              },
+                              label {
+                                  width: "flex(1)",
+                                  font_family: "{theme.font.sans}",
+                                  font_size: "{theme.size.text_m}",
+                                  color: "{theme.color.text}",
+                                  "{entry.title}"
+                              }
+                              if let Some(action) = entry.action {
+                                  for chord in keymap.chords_for(action) {
+                                      rect {
+                                          margin: "0 0 0 4",
+                                          padding: "2 6",
+                                          corner_radius: "4",
+                                          border: "1 solid {theme.color.border}",
+                                          label {
+                                              font_family: "{theme.font.mono}",
+                                              font_size: "{theme.size.text_xs}",
+                                              color: "{theme.color.text_alt}",
+                                              "{chord}"
+                                          }
+                                      }
+                                  }
+                              }
+                          }
+                      }
+                  }
+              }
+          }
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ids(entries: &[Entry]) -> Vec<&str> {
+    entries.iter().map(|entry| entry.id.as_str()).collect()
+  }
+
+  #[test]
+  fn ties_go_to_the_most_recently_used() {
+    // Every "Show …" command matches "show" as well, so they keep their order.
+    let entries = ranked_entries("show", &[]);
+    assert_eq!(ids(&entries)[..2], ["list_Best", "list_Top"]);
+    let entries = ranked_entries("show", &["list_New".to_string(), "list_Top".to_string()]);
+    assert_eq!(ids(&entries)[..3], ["list_New", "list_Top", "list_Best"]);
+  }
+
+  #[test]
+  fn better_matches_come_before_recent_ones() {
+    let entries = ranked_entries("refresh", &["toggle_theme".to_string()]);
+    assert_eq!(ids(&entries)[0], "refresh");
+  }
+
+  #[test]
+  fn an_empty_query_lists_only_the_fixed_commands() {
+    let entries = ranked_entries(" ", &[]);
+    assert_eq!(entries.len(), fixed_entries().len());
+  }
+
+  #[test]
+  fn story_ids_are_opened_first() {
+    let entries = ranked_entries("123", &[]);
+    assert_eq!(ids(&entries), ["open_story", "open_user", "search_text"]);
+    assert!(entries[0].run == Run::FetchStory(123));
+    let entries = ranked_entries("#123", &[]);
+    assert_eq!(ids(&entries), ["open_story", "search_text"]);
+  }
+
+  #[test]
+  fn the_user_and_search_entries_come_last() {
+    let entries = ranked_entries("show", &[]);
+    let ids = ids(&entries);
+    assert!(ids.len() > 2);
+    assert_eq!(ids[ids.len() - 2..], ["open_user", "search_text"]);
+    assert!(entries.last().unwrap().run == Run::Command(Command::Search(Some("show".to_string()))));
+  }
+
+  #[test]
+  fn usernames_are_2_to_15_characters() {
+    assert!(!is_username("a"));
+    assert!(is_username("pg"));
+    assert!(is_username("dang_-123"));
+    assert!(is_username("abcdefghijklmno"));
+    assert!(!is_username("abcdefghijklmnop"));
+    assert!(!is_username("two words"));
+    assert!(!is_username("#123"));
+  }
+}
@@ -0,0 +1,64 @@
+use crate::components::icons::IconComments;
+use crate::components::primitives::Spacer;
+use crate::theme::Theme;
+use crate::utils::datetime::format_timestamp;
+use crate::utils::html;
+use freya::prelude::*;
+use jiff::Timestamp;
+
+/// A comment shown on its own, outside its thread, with the start of its text.
+#[component]
+pub fn CommentCard(
+  /// The comment's HTML.
+  text: Option<String>,
+  time: Option<Timestamp>,
+  /// The author, when it is not clear from where the card is shown.
+  #[props(default)]
+  by: Option<String>,
+) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  let text = text.as_deref().map(html::to_plain_text).unwrap_or_default();
+  let mut heading = match &by {
+    Some(by) => format!("Comment by {by}"),
+    None => "Comment".to_string(),
+  };
+  if let Some(time) = &time {
+    heading.push_str(&format!(" · {}", format_timestamp(time)));
+  }
+
+  rsx! {
+      rect {
+          width: "100%",
+          height: "auto",
+          direction: "vertical",
+          padding: "12 16",
+          margin: "0 0 8 0",
+          corner_radius: "8",
+          background: "{theme.color.background_card}",
+          border: "1 solid {theme.color.border}",
+          rect {
+              direction: "horizontal",
+              cross_align: "center",
+              IconComments {}
+              Spacer { width: "4" }
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "{heading}"
+              }
+          }
+          Spacer { height: "6" }
+          label {
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_m}",
+              color: "{theme.color.text}",
+              max_lines: "4",
+              text_overflow: "ellipsis",
+              "{text}"
+          }
+      }
+  }
+}
@@ -1,9 +1,14 @@
+use crate::components::author_link::AuthorLink;
+use crate::components::bookmark_star::BookmarkStar;
 use crate::components::icons::{IconTime, IconUser};
 use crate::components::indication_label::IndicationLabel;
 use crate::components::primitives::Spacer;
+use crate::components::rich_text::RichText;
 use crate::models::{Comment, FetchState};
 use crate::theme::Theme;
+use crate::utils::bookmarks::SavedItem;
 use crate::utils::datetime::format_timestamp;
+use crate::utils::mutes::Mutes;
 use freya::prelude::*;
 
 #[derive(Props, PartialEq, Clone)]
@@ -12,6 +17,32 @@ pub struct CommentViewProps {
   pub depth: u16,
   pub on_toggle_expand: EventHandler<u32>,
   pub on_retry_fetch: EventHandler<u32>,
+  /// Whether the keyboard focus cursor is on this comment.
+  #[props(default)]
+  pub is_focused: bool,
+  /// Whether the comment is new since the last visit to the story.
+  #[props(default)]
+  pub is_new: bool,
+}
+
+// A small text button in the comment header.
+#[component]
+fn ActionLink(text: &'static str, onclick: EventHandler<()>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      CursorArea {
+          icon: CursorIcon::Pointer,
+          label {
+              onclick: move |_| onclick.call(()),
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_xs}",
+              color: "{theme.color.link}",
+              "{text}"
+          }
+      }
+  }
 }
 
 #[component]
@@ -22,22 +53,21 @@ pub fn CommentView(props: CommentViewProps) -> Element {
   let has_kids = comment.kids.as_ref().is_some_and(|k| !k.is_empty());
   let comment_id = comment.id;
   let fetch_state = comment.fetch_state.read().clone();
+  let mut mutes = use_context::<Signal<Mutes>>();
+  // Comments by muted users are collapsed to a line unless shown on request;
+  // their replies stay.
+  let mut revealed = use_signal(|| false);
+  let is_collapsed = mutes.read().hides_comment(comment) && !*revealed.read();
+  let author = comment.by.clone();
 
   const INDENTATION_SIZE: u16 = 20;
   const DELETED_TEXT: &str = "[deleted]";
 
   let padding_left = (props.depth * INDENTATION_SIZE).to_string();
-
-  let display_text = if comment.deleted {
-    DELETED_TEXT.to_string()
+  let border = if props.is_focused {
+    format!("2 inner {}", theme.color.focus_ring)
   } else {
-    match &comment.text {
-      Some(text) => match html2text::from_read(text.as_bytes(), 80) {
-        Ok(plain_text) => plain_text,
-        Err(_) => "[failed to parse comment]".to_string(),
-      },
-      None => "".to_string(),
-    }
+    format!("1 solid {}", theme.color.border)
   };
 
   rsx! {
@@ -46,10 +76,12 @@ pub fn CommentView(props: CommentViewProps) -> Element {
           direction: "vertical",
           width: "100%",
           padding: "8 0 8 {padding_left}",
-          border: "1 solid {theme.color.border}",
+          border: "{border}",
 
           rect {
+              width: "100%",
               direction: "horizontal",
+              content: "flex",
               cross_align: "center",
               if has_kids {
                   rect {
@@ -73,17 +105,29 @@ pub fn CommentView(props: CommentViewProps) -> Element {
                   }
                   Spacer { width: "8" }
               }
-              if !comment.deleted {
-                  rect {
-                      direction: "horizontal",
-                      cross_align: "center",
-                      IconUser {},
-                      Spacer { width: "4" },
-                      label {
-                          font_family: "{theme.font.sans}",
-                          font_size: "{theme.size.text_s}",
-                          color: "{theme.color.text_alt}",
-                          "{comment.by.as_deref().unwrap_or(\"N/A\")}"
+              if is_collapsed {
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text_alt}",
+                      "Comment by a muted user"
+                  }
+                  Spacer { width: "8" }
+                  ActionLink { text: "show", onclick: move |_| revealed.set(true) }
+              } else if !comment.deleted {
+                  AuthorLink {
+                      name: comment.by.clone(),
+                      rect {
+                          direction: "horizontal",
+                          cross_align: "center",
+                          IconUser {},
+                          Spacer { width: "4" },
+                          label {
+                              font_family: "{theme.font.sans}",
+                              font_size: "{theme.size.text_s}",
+                              color: "{theme.color.text_alt}",
+                              "{comment.by.as_deref().unwrap_or(\"N/A\")}"
+                          }
                       }
                   }
                   Spacer { width: "12" }
@@ -102,14 +146,41 @@ pub fn CommentView(props: CommentViewProps) -> Element {
                       }
                   }
               }
+              if props.is_new {
+                  Spacer { width: "12" }
+                  rect {
+                      padding: "1 6",
+                      corner_radius: "4",
+                      background: "{theme.color.accent}",
+                      label {
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_xs}",
+                          font_weight: "{theme.font_weight.bold}",
+                          color: "{theme.color.accent_text}",
+                          "NEW"
+                      }
+                  }
+              }
+              if !comment.deleted && !is_collapsed {
+                  rect { width: "flex(1)" }
+                  if let Some(author) = author {
+                      ActionLink { text: "mute", onclick: move |_| mutes.write().mute_user(&author) }
+                      Spacer { width: "4" }
+                  }
+                  BookmarkStar { item: SavedItem::Comment(comment.into()) }
+              }
           }
           Spacer { height: "6" }
 
-          label {
-              font_family: "{theme.font.sans}",
-              font_size: "{theme.size.text_l}",
-              color: if comment.deleted { "{theme.color.text_alt}" } else { "{theme.color.text}" },
-              "{display_text}"
+          if comment.deleted {
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_l}",
+                  color: "{theme.color.text_alt}",
+                  "{DELETED_TEXT}"
+              }
+          } else if let Some(text) = comment.text.clone().filter(|_| !is_collapsed) {
+              RichText { html: text, highlight: true }
           }
 
           if *comment.is_expanded.read() {
@@ -117,19 +188,21 @@ pub fn CommentView(props: CommentViewProps) -> Element {
                   FetchState::Loading => rsx!{
                       IndicationLabel { text: "Loading replies...".to_string() }
                   },
-                  FetchState::Failed => rsx!{
+                  FetchState::Failed(error) => rsx!{
                       rect {
                           direction: "horizontal",
                           cross_align: "center",
                           padding: "4 0",
-                          label { font_family: "{theme.font.sans}", color: "red", "Failed to load replies." }
-                          Spacer { width: "8" }
-                          // This can remain a default Button as it's simple.
-                          Button {
-                              onclick: move |_| props.on_retry_fetch.call(comment_id),
-                              label {
-                                  font_family: "{theme.font.sans}",
-                                  "Retry"
+                          label { font_family: "{theme.font.sans}", color: "red", "Failed to load replies: {error}." }
+                          if error.is_retryable() {
+                              Spacer { width: "8" }
+                              // This can remain a default Button as it's simple.
+                              Button {
+                                  onclick: move |_| props.on_retry_fetch.call(comment_id),
+                                  label {
+                                      font_family: "{theme.font.sans}",
+                                      "Retry"
+                                  }
                               }
                           }
                       }
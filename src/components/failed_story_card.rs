@@ -0,0 +1,65 @@
+use crate::components::primitives::Spacer;
+use crate::models::FailedStory;
+use crate::theme::Theme;
+use freya::prelude::*;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct FailedStoryCardProps {
+  pub failure: FailedStory,
+  pub on_retry: EventHandler<u32>,
+}
+
+/// A placeholder card for a story whose details could not be loaded.
+#[component]
+pub fn FailedStoryCard(props: FailedStoryCardProps) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  const CARD_MARGIN: &str = "0 0 8 0";
+  const CARD_CORNER_RADIUS: &str = "8";
+  const CARD_PADDING: &str = "12 16";
+
+  let story_id = props.failure.id;
+
+  rsx! {
+      rect {
+          key: "failed-{story_id}",
+          width: "100%",
+          height: "auto",
+          direction: "horizontal",
+          cross_align: "center",
+          padding: CARD_PADDING,
+          margin: CARD_MARGIN,
+          corner_radius: CARD_CORNER_RADIUS,
+          background: "{theme.color.background_card}",
+          border: "1 solid {theme.color.border}",
+
+          rect {
+              width: "flex(1)",
+              direction: "vertical",
+              label {
+                  font_family: "{theme.font.serif}",
+                  font_size: "{theme.size.text_l}",
+                  color: "{theme.color.text_alt}",
+                  "Story #{story_id} could not be loaded"
+              }
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "{props.failure.error}"
+              }
+          }
+          if props.failure.error.is_retryable() {
+              Spacer { width: "8" }
+              Button {
+                  onclick: move |_| props.on_retry.call(story_id),
+                  label {
+                      font_family: "{theme.font.sans}",
+                      "Retry"
+                  }
+              }
+          }
+      }
+  }
+}
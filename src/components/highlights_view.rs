@@ -0,0 +1,118 @@
+use crate::components::primitives::{Chip, Spacer};
+use crate::components::rule_form::{RuleRow, SectionTitle, TextRuleForm};
+use crate::router::{use_router, use_scroll_restoration};
+use crate::theme::{HighlightColor, Theme};
+use crate::utils::highlights::Highlights;
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use freya::prelude::*;
+
+// --- Main Component ---
+/// The highlight rules, marking the topics the user follows in story titles and
+/// comments in a color of their choice.
+#[component]
+pub fn HighlightsView() -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut highlights = use_context::<Signal<Highlights>>();
+  let mut router = use_router();
+  let key_actions = use_key_actions();
+  let scroll_controller = use_scroll_controller(ScrollConfig::default);
+  use_scroll_restoration(scroll_controller);
+
+  // The color new rules get.
+  let mut color = use_signal(HighlightColor::default);
+
+  let current = highlights.read();
+
+  rsx! {
+      ScrollView {
+          scroll_controller,
+          width: "100%",
+          height: "fill",
+          show_scrollbar: true,
+          rect {
+              onglobalkeydown: move |e: KeyboardEvent| match key_actions.action(&e, Scope::Detail) {
+                  Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+                  Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+                  _ => {}
+              },
+              width: "100%",
+              height: "auto",
+              padding: "15",
+              direction: "vertical",
+              background: "{theme.color.background_card}",
+
+              rect {
+                  onclick: move |_| router.back(),
+                  background: "{theme.color.background_page}",
+                  border: "1 solid {theme.color.border}",
+                  padding: "6 10",
+                  corner_radius: "6",
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_m}",
+                      color: "{theme.color.text}",
+                      "← Back"
+                  }
+              }
+              Spacer { height: "12" }
+              label {
+                  font_family: "{theme.font.serif}",
+                  font_size: "{theme.size.text_xxl}",
+                  font_weight: "{theme.font_weight.bold}",
+                  "Highlights"
+              }
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "What these match is colored in story titles and comments. \"Only highlighted\" above the list shows only the stories with a highlighted title."
+              }
+
+              SectionTitle { text: "New rule" }
+              rect {
+                  direction: "horizontal",
+                  cross_align: "center",
+                  margin: "0 0 6 0",
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text_alt}",
+                      "Color:"
+                  }
+                  Spacer { width: "6" }
+                  for option in HighlightColor::ALL {
+                      Chip {
+                          title: option.to_string(),
+                          is_active: *color.read() == option,
+                          onclick: move |_| color.set(option),
+                      }
+                  }
+              }
+              TextRuleForm {
+                  keyword_placeholder: "e.g. Rust",
+                  on_add: move |rule| highlights.write().add(rule, *color.peek()),
+              }
+
+              SectionTitle { text: "Rules" }
+              if current.rules().is_empty() {
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text_alt}",
+                      "No rules yet."
+                  }
+              }
+              for (index, rule) in current.rules().iter().enumerate() {
+                  RuleRow {
+                      key: "{index}-{rule.rule:?}",
+                      text: rule.rule.to_string(),
+                      action: "Remove",
+                      color: theme.color.highlight.get(rule.color),
+                      on_remove: move |_| highlights.write().remove(index),
+                  }
+              }
+          }
+      }
+  }
+}
@@ -0,0 +1,388 @@
+use crate::components::icons::IconUser;
+use crate::components::indication_label::IndicationLabel;
+use crate::components::primitives::Spacer;
+use crate::components::story_list_view::OpenedFrom;
+use crate::models::domain;
+use crate::router::{Route, use_list_scroll_restoration, use_router};
+use crate::theme::Theme;
+use crate::utils::datetime::{format_day, format_time_of_day, local_date, parse_date};
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use crate::utils::reading_history::{HistoryEntry, ReadingHistory, Visited};
+use freya::prelude::*;
+use jiff::civil::Date;
+use jiff::{Timestamp, ToSpan};
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+const PAGE_SIZE: usize = 30;
+const SCROLL_END_MARGIN: i32 = 150;
+
+// The days the history can be cleared for at once.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ClearRange {
+  Today,
+  LastWeek,
+  LastMonth,
+  Everything,
+  /// From one day to another, both included; a missing end leaves the range open.
+  Between {
+    from: Option<Date>,
+    to: Option<Date>,
+  },
+}
+
+impl ClearRange {
+  // The presets offered as buttons.
+  const ALL: [ClearRange; 4] = [ClearRange::Today, ClearRange::LastWeek, ClearRange::LastMonth, ClearRange::Everything];
+
+  fn days(self) -> RangeInclusive<Date> {
+    let today = local_date(&Timestamp::now());
+    let first = match self {
+      ClearRange::Today => today,
+      ClearRange::LastWeek => today.saturating_sub(6.days()),
+      ClearRange::LastMonth => today.saturating_sub(29.days()),
+      ClearRange::Everything => Date::MIN,
+      ClearRange::Between { from, to } => return from.unwrap_or(Date::MIN)..=to.unwrap_or(Date::MAX),
+    };
+    first..=Date::MAX
+  }
+}
+
+impl fmt::Display for ClearRange {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let label = match self {
+      ClearRange::Today => "Today",
+      ClearRange::LastWeek => "Last 7 days",
+      ClearRange::LastMonth => "Last 30 days",
+      ClearRange::Everything => "Everything",
+      ClearRange::Between { from: Some(from), to: Some(to) } => return write!(f, "{from} to {to}"),
+      ClearRange::Between { from: Some(from), to: None } => return write!(f, "{from} onwards"),
+      ClearRange::Between { from: None, to: Some(to) } => return write!(f, "everything up to {to}"),
+      ClearRange::Between { from: None, to: None } => "Everything",
+    };
+    write!(f, "{label}")
+  }
+}
+
+// --- Local Components ---
+// A page in the history; clicking opens it again. `story_ids` are the stories the
+// detail view walks through from there.
+#[component]
+fn HistoryRow(entry: HistoryEntry, story_ids: Memo<Vec<u32>>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut router = use_router();
+  let mut opened_from = use_context::<OpenedFrom>();
+
+  let time = format_time_of_day(&entry.at);
+  let (title, detail, route) = match entry.visited {
+    Visited::Story { id, title, url } => {
+      let detail = url.as_deref().and_then(domain).map(|domain| format!("{time} · {domain}")).unwrap_or(time);
+      (title.unwrap_or_else(|| "[No Title]".to_string()), detail, Route::Item(id))
+    }
+    Visited::User(name) => (name.clone(), format!("{time} · profile"), Route::User(name)),
+  };
+  let is_user = matches!(route, Route::User(_));
+
+  rsx! {
+      rect {
+          width: "100%",
+          direction: "horizontal",
+          cross_align: "center",
+          padding: "8 12",
+          margin: "0 0 4 0",
+          corner_radius: "6",
+          background: "{theme.color.background_card}",
+          onclick: move |_| {
+              if !is_user {
+                  opened_from.ids.set(story_ids.peek().clone());
+              }
+              router.push(route.clone());
+          },
+          if is_user {
+              IconUser {}
+              Spacer { width: "6" }
+          }
+          rect {
+              direction: "vertical",
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_m}",
+                  color: "{theme.color.text}",
+                  max_lines: "1",
+                  text_overflow: "ellipsis",
+                  "{title}"
+              }
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "{detail}"
+              }
+          }
+      }
+  }
+}
+
+// --- Main Component ---
+/// The stories and profiles opened before, newest first and grouped by day, with
+/// a filter over titles, domains and usernames.
+#[component]
+pub fn HistoryView() -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut reading_history = use_context::<Signal<ReadingHistory>>();
+  let router = use_router();
+  let key_actions = use_key_actions();
+  let scroll_controller = use_scroll_controller(ScrollConfig::default);
+  use_list_scroll_restoration(scroll_controller);
+
+  let mut filter_text = use_signal(String::new);
+  let mut shown_count = use_signal(|| PAGE_SIZE);
+  // The range about to be cleared, waiting for confirmation.
+  let mut pending_clear: Signal<Option<ClearRange>> = use_signal(|| None);
+  // The days typed in to clear a range of, while choosing one.
+  let mut choosing_range = use_signal(|| false);
+  let mut from_text = use_signal(String::new);
+  let mut to_text = use_signal(String::new);
+  let mut range_error: Signal<Option<&str>> = use_signal(|| None);
+
+  // Asks to confirm clearing the range typed in, if it is one.
+  let mut confirm_range = move || {
+    let range = match (parse_date(&from_text.read()), parse_date(&to_text.read())) {
+      (Ok(from), Ok(to)) if from.zip(to).is_none_or(|(from, to)| from <= to) => ClearRange::Between { from, to },
+      (Ok(_), Ok(_)) => return range_error.set(Some("The first day is after the last")),
+      _ => return range_error.set(Some("Dates are written as YYYY-MM-DD")),
+    };
+    range_error.set(None);
+    choosing_range.set(false);
+    pending_clear.set(Some(range));
+  };
+
+  let filtered = use_memo(move || {
+    let filter = filter_text.read().trim().to_lowercase();
+    let reading_history = reading_history.read();
+    reading_history.entries().iter().filter(|entry| entry.visited.matches(&filter)).cloned().collect::<Vec<_>>()
+  });
+  // The stories shown, each once and newest first, for the detail view to walk through.
+  let story_ids = use_memo(move || {
+    let mut seen = HashSet::new();
+    filtered
+      .read()
+      .iter()
+      .filter_map(|entry| match &entry.visited {
+        Visited::Story { id, .. } => seen.insert(*id).then_some(*id),
+        Visited::User(_) => None,
+      })
+      .collect::<Vec<_>>()
+  });
+
+  // Shows the next page once scrolled near the end.
+  use_effect(move || {
+    let y_val = *scroll_controller.y().read();
+    let layout = scroll_controller.layout();
+    let layout_val = layout.read();
+    let end = layout_val.inner.height - layout_val.area.height();
+    let near_end = layout_val.inner.height > layout_val.area.height() && -y_val > end as i32 - SCROLL_END_MARGIN;
+    if near_end && *shown_count.peek() < filtered.read().len() {
+      shown_count += PAGE_SIZE;
+    }
+  });
+
+  let onglobalkeydown = move |e: KeyboardEvent| {
+    // While a page opened from the list is shown next to it, keys go to that page.
+    if !router.route().is_list() {
+      return;
+    }
+    match key_actions.action(&e, Scope::List) {
+      Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+      Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+      _ => {}
+    }
+  };
+
+  // The shown entries by day, most recent day first.
+  let mut days: Vec<(Date, Vec<HistoryEntry>)> = Vec::new();
+  for entry in filtered.read().iter().take(*shown_count.read()) {
+    let date = local_date(&entry.at);
+    match days.last_mut() {
+      Some((day, entries)) if *day == date => entries.push(entry.clone()),
+      _ => days.push((date, vec![entry.clone()])),
+    }
+  }
+  let is_empty = filtered.read().is_empty();
+  let empty_text = if reading_history.read().entries().is_empty() {
+    "Stories and profiles you open will be listed here."
+  } else {
+    "Nothing in the history matches."
+  };
+
+  rsx! {
+      rect {
+          width: "100%",
+          height: "fill",
+          direction: "vertical",
+          onglobalkeydown,
+          rect {
+              width: "100%",
+              direction: "horizontal",
+              content: "flex",
+              cross_align: "center",
+              margin: "0 0 8 0",
+              Input {
+                  width: "flex(1)",
+                  value: filter_text.read().clone(),
+                  placeholder: "Filter by title or domain",
+                  onchange: move |value| {
+                      filter_text.set(value);
+                      shown_count.set(PAGE_SIZE);
+                  },
+              }
+          }
+          rect {
+              width: "100%",
+              direction: "horizontal",
+              cross_align: "center",
+              margin: "0 0 8 0",
+              if let Some(range) = *pending_clear.read() {
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text}",
+                      "Clear the history of: {range}?"
+                  }
+                  Spacer { width: "8" }
+                  Button {
+                      onclick: move |_| {
+                          reading_history.write().clear(range.days());
+                          pending_clear.set(None);
+                      },
+                      label { "Clear" }
+                  }
+                  Spacer { width: "4" }
+                  Button {
+                      onclick: move |_| pending_clear.set(None),
+                      label { "Cancel" }
+                  }
+              } else {
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text_alt}",
+                      "Clear:"
+                  }
+                  Spacer { width: "6" }
+                  for range in ClearRange::ALL {
+                      Button {
+                          onclick: move |_| pending_clear.set(Some(range)),
+                          label { "{range}" }
+                      }
+                      Spacer { width: "4" }
+                  }
+                  Button {
+                      onclick: move |_| choosing_range.toggle(),
+                      label { "Range…" }
+                  }
+              }
+          }
+          if *choosing_range.read() && pending_clear.read().is_none() {
+              rect {
+                  width: "100%",
+                  direction: "horizontal",
+                  cross_align: "center",
+                  margin: "0 0 8 0",
+                  Input {
+                      width: "120",
+                      value: from_text.read().clone(),
+                      placeholder: "From YYYY-MM-DD",
+                      onchange: move |value| from_text.set(value),
+                  }
+                  Spacer { width: "6" }
+                  Input {
+                      width: "120",
+                      value: to_text.read().clone(),
+                      placeholder: "To YYYY-MM-DD",
+                      onchange: move |value| to_text.set(value),
+                  }
+                  Spacer { width: "6" }
+                  Button {
+                      onclick: move |_| confirm_range(),
+                      label { "Clear range" }
+                  }
+                  if let Some(error) = *range_error.read() {
+                      label {
+                          margin: "0 0 0 8",
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_s}",
+                          color: "red",
+                          "{error}"
+                      }
+                  }
+              }
+          }
+          ScrollView {
+              scroll_controller,
+              width: "100%",
+              height: "fill",
+              show_scrollbar: true,
+              if is_empty {
+                  IndicationLabel { text: empty_text }
+              }
+              for (date, entries) in days {
+                  rect {
+                      key: "{date}",
+                      width: "100%",
+                      direction: "vertical",
+                      margin: "0 0 8 0",
+                      rect {
+                          width: "100%",
+                          direction: "horizontal",
+                          content: "flex",
+                          cross_align: "center",
+                          padding: "4 0",
+                          label {
+                              width: "flex(1)",
+                              font_family: "{theme.font.sans}",
+                              font_size: "{theme.size.text_m}",
+                              font_weight: "{theme.font_weight.bold}",
+                              color: "{theme.color.text}",
+                              "{format_day(date)}"
+                          }
+                          Button {
+                              onclick: move |_| reading_history.write().clear(date..=date),
+                              label { "Clear day" }
+                          }
+                      }
+                      for entry in entries {
+                          HistoryRow { key: "{entry.at}", entry, story_ids }
+                      }
+                  }
+              }
+          }
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use jiff::civil::date;
+
+  #[test]
+  fn presets_count_back_from_today() {
+    let today = local_date(&Timestamp::now());
+    assert_eq!(ClearRange::Today.days(), today..=Date::MAX);
+    assert_eq!(ClearRange::LastWeek.days(), today.saturating_sub(6.days())..=Date::MAX);
+    assert_eq!(ClearRange::Everything.days(), Date::MIN..=Date::MAX);
+  }
+
+  #[test]
+  fn ranges_include_both_days_and_may_be_open() {
+    let (from, to) = (date(2026, 3, 1), date(2026, 3, 31));
+    assert_eq!(ClearRange::Between { from: Some(from), to: Some(to) }.days(), from..=to);
+    assert_eq!(ClearRange::Between { from: Some(from), to: None }.days(), from..=Date::MAX);
+    assert_eq!(ClearRange::Between { from: None, to: Some(to) }.days(), Date::MIN..=to);
+    assert_eq!(ClearRange::Between { from: Some(from), to: Some(to) }.to_string(), "2026-03-01 to 2026-03-31");
+  }
+}
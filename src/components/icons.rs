@@ -55,6 +55,49 @@ pub fn IconSun() -> Element {
   }
 }
 
+#[component]
+pub fn IconSearch() -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  rsx! {
+      label {
+          font_size: "{theme.size.text_xl}",
+          color: "{theme.color.accent_text}",
+          "🔍"
+      }
+  }
+}
+
+/// Dimmed while there is no page to go back to.
+#[component]
+pub fn IconBack(enabled: bool) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  rsx! {
+      label {
+          font_size: "{theme.size.text_xl}",
+          color: "{theme.color.accent_text}",
+          opacity: if enabled { "1" } else { "0.4" },
+          "←"
+      }
+  }
+}
+
+/// Dimmed while there is no page to go forward to.
+#[component]
+pub fn IconForward(enabled: bool) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  rsx! {
+      label {
+          font_size: "{theme.size.text_xl}",
+          color: "{theme.color.accent_text}",
+          opacity: if enabled { "1" } else { "0.4" },
+          "→"
+      }
+  }
+}
+
 #[component]
 pub fn IconThemeToggle() -> Element {
   let theme_mode = use_context::<Signal<ThemeMode>>();
@@ -0,0 +1,121 @@
+use crate::components::story_detail_view::StoryDetailView;
+use crate::models::Story;
+use crate::router::{ItemTab, Route, use_router};
+use crate::theme::Theme;
+use freya::prelude::*;
+use std::collections::HashMap;
+
+// --- Local Components ---
+// A tab in the strip: clicking shows the story, middle-clicking or × closes it.
+#[component]
+fn ItemTabHandle(tab: ItemTab, title: Option<String>, is_shown: bool) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut router = use_router();
+
+  let background = if is_shown { theme.color.tab_background_active } else { "transparent" };
+  let color = if is_shown { theme.color.tab_text_active } else { theme.color.tab_text_inactive };
+  let title = title.unwrap_or_else(|| format!("#{}", tab.id));
+  let ItemTab { key, id } = tab;
+
+  rsx! {
+      rect {
+          width: "flex(1)",
+          max_width: "220",
+          padding: "6 8",
+          corner_radius: "6",
+          direction: "horizontal",
+          content: "flex",
+          cross_align: "center",
+          background: "{background}",
+          onclick: move |_| router.push(Route::Item(id)),
+          onmiddleclick: move |_| router.close_tab(key),
+          label {
+              width: "flex(1)",
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_s}",
+              color: "{color}",
+              max_lines: "1",
+              text_overflow: "ellipsis",
+              "{title}"
+          }
+          rect {
+              padding: "0 4",
+              onclick: move |e: MouseEvent| {
+                  e.stop_propagation();
+                  router.close_tab(key);
+              },
+              label {
+                  font_size: "{theme.size.text_s}",
+                  color: "{color}",
+                  "×"
+              }
+          }
+      }
+  }
+}
+
+// --- Main Component ---
+/// The stories open in tabs, as a strip of tabs above the one shown. Every open
+/// story stays mounted, with the hidden ones collapsed, so that each keeps its
+/// comments, expanded threads and scroll position while another is shown.
+#[component]
+pub fn ItemTabs(selected_story: Signal<Option<Story>>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let router = use_router();
+  let mut titles: Signal<HashMap<u32, String>> = use_signal(HashMap::new);
+
+  let tabs = router.tabs();
+  let shown = router.shown_tab();
+  if tabs.is_empty() {
+    return rsx! {};
+  }
+
+  rsx! {
+      rect {
+          width: "100%",
+          height: if shown.is_some() { "fill" } else { "auto" },
+          direction: "vertical",
+
+          rect {
+              width: "100%",
+              padding: "4",
+              margin: "0 0 6 0",
+              direction: "horizontal",
+              content: "flex",
+              background: "{theme.color.background_card}",
+              border: "1 solid {theme.color.border}",
+              corner_radius: "6",
+              for tab in tabs.iter().cloned() {
+                  ItemTabHandle {
+                      key: "{tab.key}",
+                      title: titles.read().get(&tab.id).cloned(),
+                      is_shown: shown == Some(tab.key),
+                      tab,
+                  }
+              }
+          }
+
+          for tab in tabs {
+              rect {
+                  // A tab showing another story starts over with it.
+                  key: "{tab.key}-{tab.id}",
+                  width: "100%",
+                  height: if shown == Some(tab.key) { "fill" } else { "0" },
+                  overflow: "clip",
+                  StoryDetailView {
+                      id: tab.id,
+                      selected_story,
+                      is_active: shown == Some(tab.key),
+                      on_loaded: move |story: Story| {
+                          if let Some(title) = story.title {
+                              titles.write().insert(story.id, title);
+                          }
+                      },
+                  }
+              }
+          }
+      }
+  }
+}
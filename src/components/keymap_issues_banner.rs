@@ -0,0 +1,46 @@
+use crate::theme::Theme;
+use crate::utils::keymap::KeymapIssue;
+use freya::prelude::*;
+
+/// Lists the problems found in the user's keymap file until dismissed.
+#[component]
+pub fn KeymapIssuesBanner(issues: Vec<KeymapIssue>, on_dismiss: EventHandler<()>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      rect {
+          width: "100%",
+          padding: "8 10",
+          margin: "6 0 0 0",
+          corner_radius: "6",
+          background: "{theme.color.background_card}",
+          border: "1 solid {theme.color.offline_background}",
+          direction: "horizontal",
+          cross_align: "center",
+          rect {
+              width: "flex(1)",
+              direction: "vertical",
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_m}",
+                  font_weight: "{theme.font_weight.bold}",
+                  color: "{theme.color.text}",
+                  "Some key bindings in keymap.toml were ignored:"
+              }
+              for issue in issues {
+                  label {
+                      font_family: "{theme.font.mono}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text_alt}",
+                      "• {issue}"
+                  }
+              }
+          }
+          Button {
+              onclick: move |_| on_dismiss.call(()),
+              label { "Dismiss" }
+          }
+      }
+  }
+}
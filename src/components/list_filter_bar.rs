@@ -0,0 +1,136 @@
+use crate::components::primitives::{Chip, Spacer};
+use crate::theme::Theme;
+use crate::utils::list_filter::{AgeWindow, LinkFilter, ListFilter, StorySort};
+use freya::prelude::*;
+
+// A count typed into a filter field, or `None` while the field is empty. Counts too
+// large to fit are capped rather than dropped.
+fn parse_count(value: &str) -> Option<u32> {
+  value.trim().parse::<u64>().ok().map(|n| n.min(u32::MAX as u64) as u32)
+}
+
+// --- Main Component ---
+/// Sort order chips over the loaded stories, with the filters on score, comments,
+/// age and links folded away behind a "Filters" chip.
+#[component]
+pub fn ListFilterBar(filter: Signal<ListFilter>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  let initial = filter.peek().clone();
+  let mut show_filters = use_signal(|| initial.has_filters());
+  let mut min_score = use_signal(|| initial.min_score.map(|n| n.to_string()).unwrap_or_default());
+  let mut min_comments = use_signal(|| initial.min_comments.map(|n| n.to_string()).unwrap_or_default());
+
+  let current = filter.read().clone();
+
+  rsx! {
+      rect {
+          width: "100%",
+          height: "auto",
+          direction: "vertical",
+          margin: "0 0 6 0",
+          rect {
+              width: "100%",
+              direction: "horizontal",
+              cross_align: "center",
+              label {
+                  margin: "0 6 0 8",
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "Sort:"
+              }
+              for sort in StorySort::ALL {
+                  Chip {
+                      title: sort.to_string(),
+                      is_active: current.sort == sort,
+                      onclick: move |_| filter.write().sort = sort,
+                  }
+              }
+              Spacer { width: "12" }
+              Chip {
+                  title: "Filters",
+                  is_active: *show_filters.read(),
+                  onclick: move |_| show_filters.toggle(),
+              }
+              if current.is_active() {
+                  Chip {
+                      title: "Reset",
+                      is_active: false,
+                      onclick: move |_| {
+                          filter.set(ListFilter::default());
+                          min_score.set(String::new());
+                          min_comments.set(String::new());
+                      },
+                  }
+              }
+          }
+          if *show_filters.read() {
+              Spacer { height: "6" }
+              rect {
+                  direction: "horizontal",
+                  cross_align: "center",
+                  Input {
+                      width: "100",
+                      value: min_score.read().clone(),
+                      placeholder: "Min points",
+                      onchange: move |value: String| {
+                          if value.chars().all(|c| c.is_ascii_digit()) {
+                              filter.write().min_score = parse_count(&value);
+                              min_score.set(value);
+                          }
+                      },
+                  }
+                  Spacer { width: "6" }
+                  Input {
+                      width: "120",
+                      value: min_comments.read().clone(),
+                      placeholder: "Min comments",
+                      onchange: move |value: String| {
+                          if value.chars().all(|c| c.is_ascii_digit()) {
+                              filter.write().min_comments = parse_count(&value);
+                              min_comments.set(value);
+                          }
+                      },
+                  }
+              }
+              Spacer { height: "6" }
+              rect {
+                  direction: "horizontal",
+                  cross_align: "center",
+                  for age in AgeWindow::ALL {
+                      Chip {
+                          title: age.to_string(),
+                          is_active: current.age == age,
+                          onclick: move |_| filter.write().age = age,
+                      }
+                  }
+                  Spacer { width: "12" }
+                  for link in LinkFilter::ALL {
+                      Chip {
+                          title: link.to_string(),
+                          is_active: current.link == link,
+                          onclick: move |_| filter.write().link = link,
+                      }
+                  }
+              }
+          }
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn counts_are_parsed_and_capped() {
+    assert_eq!(parse_count(""), None);
+    assert_eq!(parse_count("  "), None);
+    assert_eq!(parse_count("abc"), None);
+    assert_eq!(parse_count("-5"), None);
+    assert_eq!(parse_count(" 42 "), Some(42));
+    assert_eq!(parse_count("99999999999"), Some(u32::MAX));
+  }
+}
@@ -1,16 +1,47 @@
+pub mod author_link;
+pub mod bookmark_star;
 pub mod card_footer;
+pub mod command_palette;
+pub mod comment_card;
 pub mod comment_view;
+pub use command_palette::CommandPalette;
+pub mod failed_story_card;
 pub mod footer_label;
+pub mod highlights_view;
+pub use highlights_view::HighlightsView;
+pub mod history_view;
+pub use history_view::HistoryView;
 pub mod icons;
 pub mod indication_label;
 pub mod info_line;
-pub mod no_story_selected_view;
+pub mod item_tabs;
+pub use item_tabs::ItemTabs;
+pub mod keymap_issues_banner;
+pub use keymap_issues_banner::KeymapIssuesBanner;
+pub mod list_filter_bar;
+pub use list_filter_bar::ListFilterBar;
+pub mod mute_menu;
+pub mod mutes_view;
+pub use mutes_view::MutesView;
+pub mod offline_badge;
+pub use offline_badge::OfflineBadge;
+pub mod poll_options;
 pub mod primitives;
+pub mod rich_text;
+pub mod rule_form;
+pub mod saved_view;
+pub use saved_view::SavedView;
+pub mod scroll_into_view;
+pub mod search_bar;
+pub use search_bar::SearchBar;
 pub mod skeletons;
+pub mod split_pane;
+pub use split_pane::{ShownPanes, SplitPane};
 pub mod story_card;
 pub mod story_detail_view;
-pub use story_detail_view::StoryDetailView;
 pub mod story_list_view;
 pub use story_list_view::StoryListView;
 pub mod story_tab;
 pub use story_tab::StoryTab;
+pub mod user_view;
+pub use user_view::UserView;
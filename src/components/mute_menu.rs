@@ -0,0 +1,77 @@
+use crate::models::Story;
+use crate::theme::Theme;
+use crate::utils::mutes::Mutes;
+use freya::prelude::*;
+
+// --- Local Components ---
+#[component]
+fn MenuItem(text: String, onclick: EventHandler<()>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      rect {
+          onclick: move |e: MouseEvent| {
+              // The menu is shown inside a clickable card; don't open it as well.
+              e.stop_propagation();
+              onclick.call(());
+          },
+          background: "{theme.color.background_page}",
+          border: "1 solid {theme.color.border}",
+          padding: "4 8",
+          margin: "0 4 0 0",
+          corner_radius: "6",
+          label {
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_s}",
+              color: "{theme.color.text}",
+              max_lines: "1",
+              "{text}"
+          }
+      }
+  }
+}
+
+// --- Main Component ---
+/// Ways to leave a story out of the lists: on its own, by its domain or by its
+/// author. Closes once one is picked.
+#[component]
+pub fn MuteMenu(story: Story, on_close: EventHandler<()>) -> Element {
+  let mut mutes = use_context::<Signal<Mutes>>();
+  let domain = story.domain().map(str::to_string);
+  let author = story.by.clone();
+
+  rsx! {
+      rect {
+          direction: "horizontal",
+          cross_align: "center",
+          padding: "6 0 0 0",
+          MenuItem {
+              text: "Hide story",
+              onclick: move |_| {
+                  mutes.write().hide_story(&story);
+                  on_close.call(());
+              },
+          }
+          if let Some(domain) = domain {
+              MenuItem {
+                  text: "Mute {domain}",
+                  onclick: move |_| {
+                      mutes.write().mute_domain(&domain);
+                      on_close.call(());
+                  },
+              }
+          }
+          if let Some(author) = author {
+              MenuItem {
+                  text: "Mute {author}",
+                  onclick: move |_| {
+                      mutes.write().mute_user(&author);
+                      on_close.call(());
+                  },
+              }
+          }
+          MenuItem { text: "Cancel", onclick: move |_| on_close.call(()) }
+      }
+  }
+}
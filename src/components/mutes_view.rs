@@ -0,0 +1,125 @@
+use crate::components::primitives::Spacer;
+use crate::components::rule_form::{AddField, RuleRow, SectionTitle, TextRuleForm};
+use crate::router::{use_router, use_scroll_restoration};
+use crate::theme::Theme;
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use crate::utils::mutes::Mutes;
+use freya::prelude::*;
+
+// --- Main Component ---
+/// The hidden stories and the muted domains, users and title rules, each of which
+/// can be undone, with fields to add more.
+#[component]
+pub fn MutesView() -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut mutes = use_context::<Signal<Mutes>>();
+  let mut router = use_router();
+  let key_actions = use_key_actions();
+  let scroll_controller = use_scroll_controller(ScrollConfig::default);
+  use_scroll_restoration(scroll_controller);
+
+  let current = mutes.read();
+
+  rsx! {
+      ScrollView {
+          scroll_controller,
+          width: "100%",
+          height: "fill",
+          show_scrollbar: true,
+          rect {
+              onglobalkeydown: move |e: KeyboardEvent| match key_actions.action(&e, Scope::Detail) {
+                  Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+                  Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+                  _ => {}
+              },
+              width: "100%",
+              height: "auto",
+              padding: "15",
+              direction: "vertical",
+              background: "{theme.color.background_card}",
+
+              rect {
+                  onclick: move |_| router.back(),
+                  background: "{theme.color.background_page}",
+                  border: "1 solid {theme.color.border}",
+                  padding: "6 10",
+                  corner_radius: "6",
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_m}",
+                      color: "{theme.color.text}",
+                      "← Back"
+                  }
+              }
+              Spacer { height: "12" }
+              label {
+                  font_family: "{theme.font.serif}",
+                  font_size: "{theme.size.text_xxl}",
+                  font_weight: "{theme.font_weight.bold}",
+                  "Muted"
+              }
+              label {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "Stories matching any of these are left out of the lists. Comments by muted users are collapsed."
+              }
+
+              SectionTitle { text: "Title rules" }
+              TextRuleForm {
+                  keyword_placeholder: "Word or phrase in the title",
+                  on_add: move |rule| mutes.write().add_title_rule(rule),
+              }
+              for (index, rule) in current.title_rules().iter().enumerate() {
+                  RuleRow {
+                      key: "{index}-{rule:?}",
+                      action: "Unmute",
+                      text: rule.to_string(),
+                      on_remove: move |_| mutes.write().remove_title_rule(index),
+                  }
+              }
+
+              SectionTitle { text: "Domains" }
+              AddField { placeholder: "example.com", on_add: move |domain: String| mutes.write().mute_domain(&domain) }
+              for domain in current.domains().iter().cloned() {
+                  RuleRow {
+                      key: "{domain}",
+                      action: "Unmute",
+                      text: domain.clone(),
+                      on_remove: move |_| mutes.write().unmute_domain(&domain),
+                  }
+              }
+
+              SectionTitle { text: "Users" }
+              AddField { placeholder: "Username", on_add: move |name: String| mutes.write().mute_user(&name) }
+              for name in current.users().iter().cloned() {
+                  RuleRow {
+                      key: "{name}",
+                      action: "Unmute",
+                      text: name.clone(),
+                      on_remove: move |_| mutes.write().unmute_user(&name),
+                  }
+              }
+
+              SectionTitle { text: "Hidden stories" }
+              if current.stories().is_empty() {
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_s}",
+                      color: "{theme.color.text_alt}",
+                      "Stories hidden from their ⋯ menu are listed here."
+                  }
+              }
+              for hidden in current.stories().iter().cloned() {
+                  RuleRow {
+                      key: "{hidden.id}",
+                      action: "Unmute",
+                      text: hidden.title.clone().unwrap_or_else(|| format!("#{}", hidden.id)),
+                      on_remove: move |_| mutes.write().unhide_story(hidden.id),
+                  }
+              }
+          }
+      }
+  }
+}
@@ -0,0 +1,24 @@
+use crate::theme::Theme;
+use freya::prelude::*;
+
+/// A small header badge shown while the app is serving cached data.
+#[component]
+pub fn OfflineBadge() -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      rect {
+          padding: "4 8",
+          corner_radius: "6",
+          background: "{theme.color.offline_background}",
+          label {
+              font_family: "{theme.font.mono}",
+              font_size: "{theme.size.text_xs}",
+              font_weight: "{theme.font_weight.bold}",
+              color: "{theme.color.accent_text}",
+              "OFFLINE"
+          }
+      }
+  }
+}
@@ -0,0 +1,90 @@
+use crate::components::indication_label::IndicationLabel;
+use crate::components::primitives::{ErrorView, Spacer};
+use crate::models::PollOption;
+use crate::theme::Theme;
+use crate::utils::api::ApiService;
+use freya::prelude::*;
+use futures::stream::{self, StreamExt};
+use log::info;
+use std::sync::Arc;
+
+/// The options of a poll with their vote counts, loaded from the poll's `parts`.
+#[component]
+pub fn PollOptions(parts: ReadOnlySignal<Vec<u32>>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let api_service = use_context::<Arc<ApiService>>();
+
+  let mut options_resource = use_resource(move || {
+    let parts = parts.read().clone();
+    let api_service = api_service.clone();
+    async move {
+      info!("Fetching {} poll options...", parts.len());
+      let options_futures = parts.into_iter().map(|id| {
+        let api_service = api_service.clone();
+        async move { api_service.fetch_item::<PollOption>(id).await }
+      });
+      // `buffered` keeps the options in the order the poll lists them.
+      stream::iter(options_futures).buffered(10).collect::<Vec<_>>().await.into_iter().collect::<Result<Vec<_>, _>>()
+    }
+  });
+
+  let options = match options_resource.value().read().as_ref() {
+    None => return rsx! { IndicationLabel { text: "Loading poll options..." } },
+    Some(Err(err)) => {
+      return rsx! {
+          ErrorView { error: err.clone(), on_retry: move |_| options_resource.restart() }
+      };
+    }
+    Some(Ok(options)) => options.clone(),
+  };
+
+  let total_votes: u32 = options.iter().map(|option| option.score).sum();
+  let max_votes = options.iter().map(|option| option.score).max().unwrap_or(0).max(1);
+
+  rsx! {
+      rect {
+          width: "100%",
+          direction: "vertical",
+          for option in options {
+              rect {
+                  key: "{option.id}",
+                  width: "100%",
+                  direction: "vertical",
+                  padding: "6 0",
+                  rect {
+                      width: "100%",
+                      direction: "horizontal",
+                      main_align: "space-between",
+                      label {
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_m}",
+                          color: "{theme.color.text}",
+                          "{option.text.as_deref().unwrap_or(\"[No Text]\")}"
+                      }
+                      label {
+                          font_family: "{theme.font.mono}",
+                          font_size: "{theme.size.text_s}",
+                          color: "{theme.color.text_alt}",
+                          "{option.score} votes"
+                      }
+                  }
+                  Spacer { height: "4" }
+                  rect {
+                      width: "{option.score as f32 / max_votes as f32 * 100.0}%",
+                      height: "6",
+                      corner_radius: "3",
+                      background: "{theme.color.accent}",
+                  }
+              }
+          }
+          Spacer { height: "4" }
+          label {
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_s}",
+              color: "{theme.color.text_alt}",
+              "{total_votes} votes in total"
+          }
+      }
+  }
+}
@@ -1,3 +1,5 @@
+use crate::theme::Theme;
+use crate::utils::error::ApiError;
 use freya::prelude::*;
 
 // --- Spacer Component ---
@@ -22,26 +24,73 @@ pub fn Spacer(props: SpacerProps) -> Element {
 // --- ErrorView Component ---
 #[derive(Props, PartialEq, Clone)]
 pub struct ErrorViewProps {
-  #[props(into)]
-  pub message: String,
+  pub error: ApiError,
+  /// Offered as a "Retry" button when the error is transient.
+  #[props(optional)]
+  pub on_retry: Option<EventHandler<()>>,
 }
 
 #[component]
 pub fn ErrorView(props: ErrorViewProps) -> Element {
   const ERROR_COLOR: &str = "red";
   const ERROR_FONT_SIZE: &str = "16";
+  const HINT_FONT_SIZE: &str = "13";
   const ERROR_PADDING: &str = "10";
   const CONTAINER_WIDTH: &str = "100%";
 
+  let retryable = props.error.is_retryable();
+  // Nothing was cached to fall back on while the API cannot be reached.
+  let is_offline = props.error.is_connection_error();
+
+  if is_offline {
+    return rsx! {
+        rect {
+            width: CONTAINER_WIDTH,
+            padding: ERROR_PADDING,
+            main_align: "center",
+            direction: "vertical",
+            label {
+                font_size: ERROR_FONT_SIZE,
+                "You are offline"
+            }
+            label {
+                font_size: HINT_FONT_SIZE,
+                "Nothing is cached to show instead. Try again once the connection is back."
+            }
+            if let Some(on_retry) = props.on_retry {
+                Spacer { height: "6" }
+                Button {
+                    onclick: move |_| on_retry.call(()),
+                    label { "Retry" }
+                }
+            }
+        }
+    };
+  }
+
   rsx! {
       rect {
           width: CONTAINER_WIDTH,
           padding: ERROR_PADDING,
           main_align: "center",
+          direction: "vertical",
           label {
               color: ERROR_COLOR,
               font_size: ERROR_FONT_SIZE,
-              "Error: {props.message}"
+              "Error: {props.error}"
+          }
+          if retryable {
+              label {
+                  font_size: HINT_FONT_SIZE,
+                  "This looks temporary."
+              }
+              if let Some(on_retry) = props.on_retry {
+                  Spacer { height: "6" }
+                  Button {
+                      onclick: move |_| on_retry.call(()),
+                      label { "Retry" }
+                  }
+              }
           }
       }
   }
@@ -77,3 +126,37 @@ pub fn IconButton(props: IconButtonProps) -> Element {
       }
   }
 }
+
+#[derive(Props, PartialEq, Clone)]
+pub struct ChipProps {
+  pub title: String,
+  pub is_active: bool,
+  pub onclick: EventHandler<()>,
+}
+
+/// One of a row of options to pick from, e.g. a search filter.
+#[component]
+pub fn Chip(props: ChipProps) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  let background = if props.is_active { theme.color.tab_background_active } else { "transparent" };
+  let color = if props.is_active { theme.color.tab_text_active } else { theme.color.tab_text_inactive };
+
+  rsx! {
+      rect {
+          onclick: move |_| props.onclick.call(()),
+          padding: "4 8",
+          margin: "0 4 0 0",
+          corner_radius: "6",
+          border: "1 solid {theme.color.border}",
+          background: "{background}",
+          label {
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_s}",
+              color: "{color}",
+              "{props.title}"
+          }
+      }
+  }
+}
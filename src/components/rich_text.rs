@@ -0,0 +1,180 @@
+use crate::theme::Theme;
+use crate::utils::highlights::Highlights;
+use crate::utils::html::{self, Block, Span};
+use freya::prelude::*;
+use log::warn;
+
+// --- Local Components ---
+// A paragraph of styled spans. Links are not separate elements, so a click is
+// resolved to a span through the text cursor position Freya measures for it.
+#[component]
+fn RichParagraph(spans: Vec<Span>, #[props(default)] quoted: bool, #[props(default)] highlight: bool) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let highlights = use_context::<Signal<Highlights>>();
+  let mut editable = use_editable(
+    || EditableConfig::new(String::new()).with_allow_changes(false),
+    EditableMode::MultipleLinesSingleEditor,
+  );
+
+  let onclick = {
+    let spans = spans.clone();
+    move |e: MouseEvent| {
+      editable.process_event(&EditableEvent::Click);
+      let position = editable.editor().peek().cursor_pos();
+      if let Some(url) = link_at(&spans, position) {
+        e.stop_propagation();
+        if let Err(err) = open::that(url) {
+          warn!("Failed to open {url}: {err}");
+        }
+      }
+    }
+  };
+
+  let text_color = if quoted { theme.color.text_alt } else { theme.color.text };
+  // Each span split into the runs the highlight rules color differently.
+  let runs: Vec<(&Span, String, Option<&str>)> = {
+    let highlights = highlights.read();
+    let highlight_theme = &theme.color.highlight;
+    spans
+      .iter()
+      .flat_map(|span| {
+        let runs = if highlight { highlights.runs(&span.text) } else { vec![(span.text.as_str(), None)] };
+        runs.into_iter().map(move |(text, color)| (span, text.to_string(), color.map(|c| highlight_theme.get(c))))
+      })
+      .collect()
+  };
+
+  rsx! {
+      paragraph {
+          width: "100%",
+          cursor_reference: editable.cursor_attr(),
+          cursor_id: "0",
+          cursor_mode: "editable",
+          onmousedown: move |e: MouseEvent| editable.process_event(&EditableEvent::MouseDown(e.data, 0)),
+          onclick,
+          for (span, run, highlight_color) in runs {
+              text {
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_l}",
+                  font_style: if span.italic { "italic" } else { "normal" },
+                  font_weight: if highlight_color.is_some() { "{theme.font_weight.bold}" } else { "{theme.font_weight.regular}" },
+                  color: match (highlight_color, &span.link) {
+                      (Some(color), _) => color,
+                      (None, Some(_)) => theme.color.link,
+                      (None, None) => text_color,
+                  },
+                  decoration: if span.link.is_some() { "underline" } else { "none" },
+                  "{run}"
+              }
+          }
+      }
+  }
+}
+
+// Finds the link at a cursor position, counted in UTF-16 code units like Freya's cursor.
+fn link_at(spans: &[Span], position: usize) -> Option<&str> {
+  let mut start = 0;
+  for span in spans {
+    let end = start + span.text.encode_utf16().count();
+    if let Some(link) = &span.link
+      && (start..end).contains(&position)
+    {
+      return Some(link);
+    }
+    start = end;
+  }
+  None
+}
+
+// --- Main Component ---
+/// Renders HN's HTML with paragraphs, quotes, italics, clickable links and code
+/// blocks, reflowing with the available width. With `highlight`, the words the
+/// highlight rules match are colored.
+#[component]
+pub fn RichText(html: String, #[props(default)] highlight: bool) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let blocks = html::parse(&html);
+
+  rsx! {
+      rect {
+          width: "100%",
+          direction: "vertical",
+          for (i, block) in blocks.into_iter().enumerate() {
+              match block {
+                  Block::Paragraph(spans) => rsx! {
+                      rect {
+                          key: "{i}",
+                          width: "100%",
+                          padding: "0 0 8 0",
+                          RichParagraph { spans, highlight }
+                      }
+                  },
+                  Block::Quote(spans) => rsx! {
+                      rect {
+                          key: "{i}",
+                          width: "100%",
+                          padding: "0 0 0 10",
+                          margin: "0 0 8 0",
+                          border: "0 0 0 3 solid {theme.color.border}",
+                          RichParagraph { spans, quoted: true, highlight }
+                      }
+                  },
+                  Block::Code(code) => rsx! {
+                      rect {
+                          key: "{i}",
+                          width: "100%",
+                          padding: "8",
+                          margin: "0 0 8 0",
+                          corner_radius: "4",
+                          background: "{theme.color.background_page}",
+                          border: "1 solid {theme.color.border}",
+                          paragraph {
+                              width: "100%",
+                              text {
+                                  font_family: "{theme.font.mono}",
+                                  font_size: "{theme.size.text_m}",
+                                  color: "{theme.color.text}",
+                                  "{code}"
+                              }
+                          }
+                      }
+                  },
+              }
+          }
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn span(text: &str, link: Option<&str>) -> Span {
+    Span { text: text.to_string(), italic: false, link: link.map(str::to_string) }
+  }
+
+  #[test]
+  fn links_are_found_within_their_span_only() {
+    let spans = [
+      span("see ", None),
+      span("here", Some("https://a.example")),
+      span(" and ", None),
+      span("there", Some("https://b.example")),
+    ];
+    assert_eq!(link_at(&spans, 3), None);
+    assert_eq!(link_at(&spans, 4), Some("https://a.example"));
+    assert_eq!(link_at(&spans, 7), Some("https://a.example"));
+    assert_eq!(link_at(&spans, 8), None);
+    assert_eq!(link_at(&spans, 13), Some("https://b.example"));
+    assert_eq!(link_at(&spans, 18), None);
+  }
+
+  #[test]
+  fn positions_count_utf16_code_units() {
+    let spans = [span("😀 ", None), span("link", Some("https://a.example"))];
+    assert_eq!(link_at(&spans, 2), None);
+    assert_eq!(link_at(&spans, 3), Some("https://a.example"));
+  }
+}
@@ -0,0 +1,132 @@
+//! Building blocks of the pages managing lists of rules, such as the mutes and the
+//! highlights.
+
+use crate::components::primitives::{Chip, Spacer};
+use crate::theme::Theme;
+use crate::utils::text_rule::TextRule;
+use freya::prelude::*;
+
+#[component]
+pub fn SectionTitle(text: &'static str) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      label {
+          margin: "16 0 6 0",
+          font_size: "{theme.size.text_l}",
+          font_weight: "{theme.font_weight.bold}",
+          "{text}"
+      }
+  }
+}
+
+/// An entry of a list of rules with a button undoing it.
+#[component]
+pub fn RuleRow(
+  text: String,
+  /// The label of the button, e.g. "Remove".
+  action: &'static str,
+  on_remove: EventHandler<()>,
+  /// The text color, if not the theme's.
+  #[props(default)]
+  color: Option<&'static str>,
+) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let color = color.unwrap_or(theme.color.text);
+
+  rsx! {
+      rect {
+          width: "100%",
+          direction: "horizontal",
+          content: "flex",
+          cross_align: "center",
+          padding: "4 0",
+          label {
+              width: "flex(1)",
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_m}",
+              color: "{color}",
+              max_lines: "1",
+              text_overflow: "ellipsis",
+              "{text}"
+          }
+          Button {
+              onclick: move |_| on_remove.call(()),
+              label { "{action}" }
+          }
+      }
+  }
+}
+
+/// An input adding what is typed on Enter or with the button.
+#[component]
+pub fn AddField(placeholder: &'static str, on_add: EventHandler<String>) -> Element {
+  let mut text = use_signal(String::new);
+  let mut add = move || {
+    let value = text.read().trim().to_string();
+    if !value.is_empty() {
+      on_add.call(value);
+      text.set(String::new());
+    }
+  };
+
+  rsx! {
+      rect {
+          width: "100%",
+          direction: "horizontal",
+          content: "flex",
+          cross_align: "center",
+          onkeydown: move |e: KeyboardEvent| {
+              if e.key == Key::Enter {
+                  add();
+              }
+          },
+          Input {
+              width: "flex(1)",
+              value: text.read().clone(),
+              placeholder,
+              onchange: move |value| text.set(value),
+          }
+          Spacer { width: "6" }
+          Button {
+              onclick: move |_| add(),
+              label { "Add" }
+          }
+      }
+  }
+}
+
+/// Adds a keyword or regular expression rule, showing why an invalid pattern was
+/// refused.
+#[component]
+pub fn TextRuleForm(
+  /// Example of a keyword, shown while none is typed.
+  keyword_placeholder: &'static str,
+  on_add: Callback<TextRule, Result<(), regex::Error>>,
+) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut is_regex = use_signal(|| false);
+  let mut error: Signal<Option<String>> = use_signal(|| None);
+
+  let add = move |text: String| {
+    let rule = if *is_regex.peek() { TextRule::Regex(text) } else { TextRule::Keyword(text) };
+    error.set(on_add.call(rule).err().map(|e| format!("Invalid regular expression: {e}")));
+  };
+  let placeholder = if *is_regex.read() { "e.g. ^(Ask|Tell) HN" } else { keyword_placeholder };
+
+  rsx! {
+      rect {
+          direction: "horizontal",
+          margin: "0 0 6 0",
+          Chip { title: "Keyword", is_active: !*is_regex.read(), onclick: move |_| is_regex.set(false) }
+          Chip { title: "Regular expression", is_active: *is_regex.read(), onclick: move |_| is_regex.set(true) }
+      }
+      AddField { placeholder, on_add: add }
+      if let Some(error) = error.read().as_ref() {
+          label { color: "red", font_size: "{theme.size.text_s}", "{error}" }
+      }
+  }
+}
@@ -0,0 +1,332 @@
+use crate::components::bookmark_star::BookmarkStar;
+use crate::components::comment_card::CommentCard;
+use crate::components::indication_label::IndicationLabel;
+use crate::components::primitives::{Chip, Spacer};
+use crate::components::story_card::StoryCard;
+use crate::components::story_list_view::OpenedFrom;
+use crate::models::{Item, Story};
+use crate::router::{Route, Router, use_list_scroll_restoration, use_router};
+use crate::theme::Theme;
+use crate::utils::api::ApiService;
+use crate::utils::bookmarks::{Bookmark, Bookmarks, SavedComment, SavedItem};
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use freya::prelude::*;
+use log::{info, warn};
+use std::sync::Arc;
+
+const PAGE_SIZE: usize = 20;
+const SCROLL_END_MARGIN: i32 = 150;
+
+// --- Local Components ---
+// The tags and note of a bookmark, edited in place.
+#[component]
+fn BookmarkNotes(bookmark: Bookmark) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let mut bookmarks = use_context::<Signal<Bookmarks>>();
+  let mut is_editing = use_signal(|| false);
+  let mut tags_text = use_signal(String::new);
+  let mut note_text = use_signal(String::new);
+
+  let id = bookmark.item.id();
+  let start_editing = {
+    let bookmark = bookmark.clone();
+    move |_| {
+      tags_text.set(bookmark.tags.join(", "));
+      note_text.set(bookmark.note.clone());
+      is_editing.set(true);
+    }
+  };
+  let mut save = move || {
+    let tags = tags_text.read().split(',').map(str::to_string).collect();
+    bookmarks.write().annotate(id, tags, note_text.read().clone());
+    is_editing.set(false);
+  };
+
+  if *is_editing.read() {
+    return rsx! {
+        rect {
+            width: "100%",
+            direction: "vertical",
+            margin: "0 0 12 0",
+            onkeydown: move |e: KeyboardEvent| {
+                if e.key == Key::Enter {
+                    save();
+                }
+            },
+            Input {
+                width: "100%",
+                value: tags_text.read().clone(),
+                placeholder: "Tags, separated by commas",
+                auto_focus: true,
+                onchange: move |value| tags_text.set(value),
+            }
+            Spacer { height: "6" }
+            Input {
+                width: "100%",
+                value: note_text.read().clone(),
+                placeholder: "Note",
+                onchange: move |value| note_text.set(value),
+            }
+            Spacer { height: "6" }
+            rect {
+                direction: "horizontal",
+                Button {
+                    onclick: move |_| save(),
+                    label { "Save" }
+                }
+                Spacer { width: "4" }
+                Button {
+                    onclick: move |_| is_editing.set(false),
+                    label { "Cancel" }
+                }
+            }
+        }
+    };
+  }
+
+  rsx! {
+      rect {
+          width: "100%",
+          direction: "horizontal",
+          content: "flex",
+          cross_align: "center",
+          margin: "0 0 12 0",
+          for tag in bookmark.tags.iter() {
+              label {
+                  key: "{tag}",
+                  margin: "0 6 0 0",
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.accent}",
+                  "#{tag}"
+              }
+          }
+          label {
+              width: "flex(1)",
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_s}",
+              color: "{theme.color.text_alt}",
+              max_lines: "2",
+              text_overflow: "ellipsis",
+              "{bookmark.note}"
+          }
+          // Stories have their star on the card.
+          if matches!(bookmark.item, SavedItem::Comment(_)) {
+              BookmarkStar { item: bookmark.item.clone() }
+          }
+          Button {
+              onclick: start_editing,
+              label { "Tags & note" }
+          }
+      }
+  }
+}
+
+// Opens the story a saved comment belongs to, walking up its parents. On failure,
+// returns why, to show under the comment.
+async fn open_comment_story(
+  api_service: Arc<ApiService>,
+  comment: SavedComment,
+  mut selected_story_data: Signal<Option<Story>>,
+  mut router: Router,
+) -> Result<(), String> {
+  let mut parent = comment.parent;
+  while let Some(id) = parent {
+    match api_service.fetch_item::<Item>(id).await {
+      Ok(Item::Story(story) | Item::Job(story) | Item::Poll(story)) => {
+        info!("Opening story {id} of saved comment {}", comment.id);
+        selected_story_data.set(Some(story));
+        router.push(Route::Item(id));
+        return Ok(());
+      }
+      Ok(Item::Comment(reply_to)) => parent = reply_to.parent,
+      Ok(Item::PollOpt(_) | Item::Other) => break,
+      Err(error) => {
+        warn!("Failed to find the story of saved comment {}: {}", comment.id, error);
+        return Err(format!("Could not open the story of this comment: {error}"));
+      }
+    }
+  }
+  warn!("Saved comment {} does not belong to a story", comment.id);
+  Err("This comment does not belong to a story.".to_string())
+}
+
+// --- Main Component ---
+/// The stories and comments saved for later, newest first, optionally narrowed
+/// down to one tag.
+#[component]
+pub fn SavedView(selected_story_data: Signal<Option<Story>>) -> Element {
+  let bookmarks = use_context::<Signal<Bookmarks>>();
+  let api_service = use_context::<Arc<ApiService>>();
+  let mut router = use_router();
+  let key_actions = use_key_actions();
+  let scroll_controller = use_scroll_controller(ScrollConfig::default);
+  use_list_scroll_restoration(scroll_controller);
+
+  let mut opened_from = use_context::<OpenedFrom>();
+  // The saved comment whose story is being opened, and why that failed if it did.
+  let mut opening: Signal<Option<(u32, Option<String>)>> = use_signal(|| None);
+  let mut tag_filter: Signal<Option<String>> = use_signal(|| None);
+  let mut shown_count = use_signal(|| PAGE_SIZE);
+
+  let filtered = use_memo(move || {
+    let tag_filter = tag_filter.read();
+    let bookmarks = bookmarks.read();
+    bookmarks
+      .items()
+      .iter()
+      .filter(|bookmark| tag_filter.as_ref().is_none_or(|tag| bookmark.tags.contains(tag)))
+      .cloned()
+      .collect::<Vec<_>>()
+  });
+
+  // The saved stories as filtered, for the detail view to walk through.
+  let saved_story_ids = move || {
+    filtered
+      .peek()
+      .iter()
+      .filter_map(|bookmark| match &bookmark.item {
+        SavedItem::Story(story) => Some(story.id),
+        SavedItem::Comment(_) => None,
+      })
+      .collect()
+  };
+
+  // Drops the filter once no bookmark has its tag anymore.
+  use_effect(move || {
+    let tag = tag_filter.read().clone();
+    if let Some(tag) = tag
+      && !bookmarks.read().tags().contains(&tag)
+    {
+      tag_filter.set(None);
+    }
+  });
+
+  // Shows the next page once scrolled near the end.
+  use_effect(move || {
+    let y_val = *scroll_controller.y().read();
+    let layout = scroll_controller.layout();
+    let layout_val = layout.read();
+    let end = layout_val.inner.height - layout_val.area.height();
+    let near_end = layout_val.inner.height > layout_val.area.height() && -y_val > end as i32 - SCROLL_END_MARGIN;
+    if near_end && *shown_count.peek() < filtered.read().len() {
+      shown_count += PAGE_SIZE;
+    }
+  });
+
+  let onglobalkeydown = move |e: KeyboardEvent| {
+    // While a page opened from the list is shown next to it, keys go to that page.
+    if !router.route().is_list() {
+      return;
+    }
+    match key_actions.action(&e, Scope::List) {
+      Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+      Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+      _ => {}
+    }
+  };
+
+  const EMPTY_TEXT: &str = "Nothing saved yet. Star a story or comment to keep it here.";
+  let tags = bookmarks.read().tags();
+  let filtered = filtered.read();
+  let shown = filtered.iter().take(*shown_count.read());
+
+  rsx! {
+      rect {
+          width: "100%",
+          height: "fill",
+          onglobalkeydown,
+          ScrollView {
+              scroll_controller,
+              width: "100%",
+              height: "fill",
+              show_scrollbar: true,
+              if !tags.is_empty() {
+                  rect {
+                      direction: "horizontal",
+                      cross_align: "center",
+                      margin: "0 0 8 0",
+                      Chip {
+                          title: "All",
+                          is_active: tag_filter.read().is_none(),
+                          onclick: move |_| {
+                              tag_filter.set(None);
+                              shown_count.set(PAGE_SIZE);
+                          },
+                      }
+                      for tag in tags {
+                          Chip {
+                              key: "{tag}",
+                              title: "#{tag}",
+                              is_active: tag_filter.read().as_ref() == Some(&tag),
+                              onclick: {
+                                  let tag = tag.clone();
+                                  move |_| {
+                                      tag_filter.set(Some(tag.clone()));
+                                      shown_count.set(PAGE_SIZE);
+                                  }
+                              },
+                          }
+                      }
+                  }
+              }
+              if filtered.is_empty() {
+                  IndicationLabel { text: EMPTY_TEXT }
+              }
+              for bookmark in shown {
+                  rect {
+                      key: "{bookmark.item.id()}",
+                      width: "100%",
+                      direction: "vertical",
+                      match &bookmark.item {
+                          SavedItem::Story(story) => rsx! {
+                              StoryCard {
+                                  story: story.clone(),
+                                  on_select: {
+                                      let story = story.clone();
+                                      move |_| {
+                                          opened_from.ids.set(saved_story_ids());
+                                          selected_story_data.set(Some(story.clone()));
+                                          router.push(Route::Item(story.id));
+                                      }
+                                  }
+                              }
+                          },
+                          SavedItem::Comment(comment) => rsx! {
+                              rect {
+                                  width: "100%",
+                                  onclick: {
+                                      let api_service = api_service.clone();
+                                      let comment = comment.clone();
+                                      move |_| {
+                                          opened_from.ids.set(Vec::new());
+                                          opening.set(Some((comment.id, None)));
+                                          let opened = open_comment_story(api_service.clone(), comment.clone(), selected_story_data, router);
+                                          let id = comment.id;
+                                          spawn(async move {
+                                              match opened.await {
+                                                  Ok(()) => opening.set(None),
+                                                  Err(reason) => opening.set(Some((id, Some(reason)))),
+                                              }
+                                          });
+                                      }
+                                  },
+                                  CommentCard { text: comment.text.clone(), time: comment.time, by: comment.by.clone() }
+                                  match opening.read().as_ref().filter(|(id, _)| *id == comment.id) {
+                                      Some((_, None)) => rsx! { IndicationLabel { text: "Opening the story..." } },
+                                      Some((_, Some(reason))) => rsx! {
+                                          label { color: "red", margin: "4 12", "{reason}" }
+                                      },
+                                      None => rsx! {},
+                                  }
+                              }
+                          },
+                      }
+                      BookmarkNotes { bookmark: bookmark.clone() }
+                  }
+              }
+          }
+      }
+  }
+}
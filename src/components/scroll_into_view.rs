@@ -0,0 +1,49 @@
+use freya::prelude::*;
+
+/// Scrolls its children into the visible area of `scroll_controller`'s scroll view
+/// whenever `active` becomes true, e.g. when the keyboard focus cursor lands on them.
+#[component]
+pub fn ScrollIntoView(active: ReadOnlySignal<bool>, scroll_controller: ScrollController, children: Element) -> Element {
+  let (reference, layout) = use_node_signal();
+  // Set when `active` becomes true, until the children have been laid out and scrolled to.
+  let mut pending = use_signal(|| false);
+
+  use_effect(move || {
+    if *active.read() {
+      pending.set(true);
+    }
+  });
+
+  // The layout is only subscribed to while pending, so scrolling with the mouse
+  // afterwards is not undone.
+  use_effect(move || {
+    if !*pending.read() {
+      return;
+    }
+    let area = layout.read().area;
+    // Not laid out yet, e.g. right after the list was recreated.
+    if area.is_empty() {
+      return;
+    }
+    pending.set(false);
+    let viewport = scroll_controller.layout().peek().area;
+    let mut y = scroll_controller.y();
+    let current = *y.peek();
+    if area.min_y() < viewport.min_y() {
+      y.set(current + (viewport.min_y() - area.min_y()) as i32);
+    } else if area.max_y() > viewport.max_y() {
+      // Items taller than the viewport are aligned to its top instead.
+      let overflow = (area.max_y() - viewport.max_y()).min(area.min_y() - viewport.min_y());
+      y.set(current - overflow as i32);
+    }
+  });
+
+  rsx! {
+      rect {
+          reference,
+          width: "100%",
+          height: "auto",
+          {children}
+      }
+  }
+}
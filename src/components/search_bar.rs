@@ -0,0 +1,188 @@
+use crate::components::primitives::{Chip, Spacer};
+use crate::theme::Theme;
+use crate::utils::datetime::parse_date;
+use crate::utils::search::{DateRange, SearchQuery, SearchSort, SearchTag};
+use freya::prelude::*;
+
+// --- Main Component ---
+#[derive(Props, PartialEq, Clone)]
+pub struct SearchBarProps {
+  /// The query currently shown, used to prefill the fields.
+  pub initial: Option<SearchQuery>,
+  pub on_search: EventHandler<SearchQuery>,
+  pub on_close: EventHandler<()>,
+}
+
+/// Search input with filters for the item type, author, date range, points and sort order.
+#[component]
+pub fn SearchBar(props: SearchBarProps) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  let initial = props.initial.clone().unwrap_or_default();
+  let mut text = use_signal(|| initial.text.clone());
+  let mut tag = use_signal(|| initial.tag);
+  let mut author = use_signal(|| initial.author.clone().unwrap_or_default());
+  let mut date_range = use_signal(|| initial.date_range);
+  let (initial_from, initial_to) = match initial.date_range {
+    DateRange::Between { from, to } => (from, to),
+    _ => (None, None),
+  };
+  let mut from_text = use_signal(|| initial_from.map(|date| date.to_string()).unwrap_or_default());
+  let mut to_text = use_signal(|| initial_to.map(|date| date.to_string()).unwrap_or_default());
+  let mut date_error = use_signal(|| false);
+  let mut min_points = use_signal(|| initial.min_points.map(|p| p.to_string()).unwrap_or_default());
+  let mut sort = use_signal(|| initial.sort);
+
+  let mut submit = move || {
+    let date_range = match *date_range.read() {
+      DateRange::Between { .. } => match (parse_date(&from_text.read()), parse_date(&to_text.read())) {
+        (Ok(from), Ok(to)) => DateRange::Between { from, to },
+        _ => {
+          date_error.set(true);
+          return;
+        }
+      },
+      preset => preset,
+    };
+    date_error.set(false);
+    let author = author.read().trim().to_string();
+    props.on_search.call(SearchQuery {
+      text: text.read().trim().to_string(),
+      tag: *tag.read(),
+      author: (!author.is_empty()).then_some(author),
+      date_range,
+      min_points: min_points.read().trim().parse().ok(),
+      sort: *sort.read(),
+    });
+  };
+
+  let is_between = matches!(*date_range.read(), DateRange::Between { .. });
+
+  rsx! {
+      rect {
+          width: "100%",
+          height: "auto",
+          direction: "vertical",
+          padding: "8",
+          margin: "0 0 6 0",
+          background: "{theme.color.background_card}",
+          border: "1 solid {theme.color.border}",
+          corner_radius: "6",
+          // Enter bubbles up from the focused input; the list's shortcuts ignore it.
+          onkeydown: move |e: KeyboardEvent| {
+              if e.key == Key::Enter {
+                  submit();
+              }
+          },
+
+          rect {
+              width: "100%",
+              direction: "horizontal",
+              content: "flex",
+              cross_align: "center",
+              Input {
+                  width: "flex(1)",
+                  value: text.read().clone(),
+                  placeholder: "Search Hacker News",
+                  auto_focus: true,
+                  onchange: move |value| text.set(value),
+              }
+              Spacer { width: "8" }
+              Button {
+                  onclick: move |_| submit(),
+                  label { "Search" }
+              }
+              Spacer { width: "4" }
+              Button {
+                  onclick: move |_| props.on_close.call(()),
+                  label { "✕" }
+              }
+          }
+          Spacer { height: "6" }
+          rect {
+              direction: "horizontal",
+              cross_align: "center",
+              for search_tag in SearchTag::ALL {
+                  Chip {
+                      title: search_tag.to_string(),
+                      is_active: *tag.read() == search_tag,
+                      onclick: move |_| tag.set(search_tag),
+                  }
+              }
+              Spacer { width: "12" }
+              for search_sort in [SearchSort::Relevance, SearchSort::Date] {
+                  Chip {
+                      title: format!("By {search_sort}"),
+                      is_active: *sort.read() == search_sort,
+                      onclick: move |_| sort.set(search_sort),
+                  }
+              }
+          }
+          Spacer { height: "6" }
+          rect {
+              direction: "horizontal",
+              cross_align: "center",
+              Input {
+                  width: "140",
+                  value: author.read().clone(),
+                  placeholder: "Author",
+                  onchange: move |value| author.set(value),
+              }
+              Spacer { width: "6" }
+              Input {
+                  width: "100",
+                  value: min_points.read().clone(),
+                  placeholder: "Min points",
+                  onchange: move |value: String| {
+                      if value.chars().all(|c| c.is_ascii_digit()) {
+                          min_points.set(value);
+                      }
+                  },
+              }
+              Spacer { width: "12" }
+              for range in DateRange::ALL {
+                  Chip {
+                      title: range.to_string(),
+                      is_active: *date_range.read() == range,
+                      onclick: move |_| date_range.set(range),
+                  }
+              }
+              Chip {
+                  title: "Between…",
+                  is_active: is_between,
+                  onclick: move |_| date_range.set(DateRange::Between { from: None, to: None }),
+              }
+          }
+          if is_between {
+              Spacer { height: "6" }
+              rect {
+                  direction: "horizontal",
+                  cross_align: "center",
+                  Input {
+                      width: "120",
+                      value: from_text.read().clone(),
+                      placeholder: "From YYYY-MM-DD",
+                      onchange: move |value| from_text.set(value),
+                  }
+                  Spacer { width: "6" }
+                  Input {
+                      width: "120",
+                      value: to_text.read().clone(),
+                      placeholder: "To YYYY-MM-DD",
+                      onchange: move |value| to_text.set(value),
+                  }
+                  if *date_error.read() {
+                      label {
+                          margin: "0 0 0 8",
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_s}",
+                          color: "red",
+                          "Dates are written as YYYY-MM-DD"
+                      }
+                  }
+              }
+          }
+      }
+  }
+}
@@ -0,0 +1,141 @@
+use crate::theme::Theme;
+use crate::utils::store;
+use freya::prelude::*;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+// Where the divider position is kept between sessions, in the data directory.
+const LAYOUT_FILE: &str = "layout.json";
+const DEFAULT_RATIO: f32 = 0.4;
+// Keeps either pane from being dragged out of sight.
+const MIN_RATIO: f32 = 0.2;
+const MAX_RATIO: f32 = 0.8;
+const HANDLE_WIDTH: &str = "8";
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct SplitLayout {
+  // The share of the width taken by the left pane.
+  ratio: f32,
+}
+
+impl Default for SplitLayout {
+  fn default() -> Self {
+    Self { ratio: DEFAULT_RATIO }
+  }
+}
+
+/// Which panes of a `SplitPane` are on screen.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ShownPanes {
+  Both,
+  Left,
+  Right,
+}
+
+// --- Main Component ---
+/// Shows two panes side by side, with a divider that can be dragged to resize
+/// them. The divider position is remembered across sessions. When only one pane
+/// is shown, the other is moved off screen rather than removed, so that it keeps
+/// its state.
+#[component]
+pub fn SplitPane(left: Element, right: Element, #[props(default = ShownPanes::Both)] shown: ShownPanes) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let (node_reference, layout) = use_node_signal();
+  let mut ratio = use_signal(|| store::load::<SplitLayout>(LAYOUT_FILE).ratio.clamp(MIN_RATIO, MAX_RATIO));
+  let mut is_dragging = use_signal(|| false);
+  let mut is_hovered = use_signal(|| false);
+
+  // Moves the divider to the cursor while dragging, wherever the cursor is.
+  let onglobalmousemove = move |e: MouseEvent| {
+    if !*is_dragging.peek() {
+      return;
+    }
+    let area = layout.peek().area;
+    if area.width() > 0.0 {
+      let x = e.get_screen_coordinates().x as f32 - area.min_x();
+      ratio.set((x / area.width()).clamp(MIN_RATIO, MAX_RATIO));
+    }
+  };
+
+  // Releasing the mouse button anywhere ends the drag.
+  let onglobalclick = move |_| {
+    if *is_dragging.peek() {
+      is_dragging.set(false);
+      let ratio = *ratio.peek();
+      info!("Saving the split pane divider at {:.0}%", ratio * 100.0);
+      store::save(LAYOUT_FILE, &SplitLayout { ratio });
+    }
+  };
+
+  let (left_width, right_width) = match shown {
+    ShownPanes::Both => {
+      let ratio = *ratio.read();
+      (format!("flex({ratio})"), format!("flex({})", 1.0 - ratio))
+    }
+    ShownPanes::Left | ShownPanes::Right => ("100%".to_string(), "100%".to_string()),
+  };
+  let offset_x = if shown == ShownPanes::Right { -layout.read().area.width() } else { 0.0 };
+  let handle_color =
+    if *is_dragging.read() || *is_hovered.read() { theme.color.focus_ring } else { theme.color.border };
+
+  rsx! {
+      rect {
+          reference: node_reference,
+          width: "100%",
+          height: "fill",
+          direction: "horizontal",
+          content: "flex",
+          overflow: "clip",
+          offset_x: "{offset_x}",
+          onglobalmousemove,
+          onglobalclick,
+          rect {
+              width: "{left_width}",
+              height: "100%",
+              direction: "vertical",
+              {left}
+          }
+          if shown == ShownPanes::Both {
+              CursorArea {
+                  icon: CursorIcon::ColResize,
+                  rect {
+                      width: HANDLE_WIDTH,
+                      height: "100%",
+                      main_align: "center",
+                      cross_align: "center",
+                      onmousedown: move |e: MouseEvent| {
+                          e.stop_propagation();
+                          is_dragging.set(true);
+                      },
+                      onmouseenter: move |_| is_hovered.set(true),
+                      onmouseleave: move |_| is_hovered.set(false),
+                      rect {
+                          width: "2",
+                          height: "100%",
+                          background: "{handle_color}",
+                      }
+                  }
+              }
+          }
+          rect {
+              width: "{right_width}",
+              height: "100%",
+              direction: "vertical",
+              {right}
+          }
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn a_layout_file_without_a_ratio_uses_the_default() {
+    assert_eq!(serde_json::from_str::<SplitLayout>("{}").unwrap().ratio, DEFAULT_RATIO);
+    assert_eq!(serde_json::from_str::<SplitLayout>(r#"{ "ratio": 0.6 }"#).unwrap().ratio, 0.6);
+  }
+}
@@ -1,18 +1,34 @@
+use crate::components::bookmark_star::BookmarkStar;
 use crate::components::card_footer::CardFooter;
+use crate::components::mute_menu::MuteMenu;
 use crate::models::Story;
+use crate::router::use_router;
 use crate::theme::Theme;
+use crate::utils::bookmarks::SavedItem;
+use crate::utils::highlights::Highlights;
+use crate::utils::read_stories::ReadStories;
 use freya::prelude::*;
 
 #[derive(Props, PartialEq, Clone)]
 pub struct StoryCardProps {
   pub story: Story,
   pub on_select: EventHandler<u32>,
+  /// Whether the keyboard focus cursor is on this card.
+  #[props(default)]
+  pub is_focused: bool,
 }
 
+/// A story in a list, with a dimmed title once it has been read. Clicking selects
+/// it; middle-clicking opens it in a tab in the background. The ⋯ button offers to
+/// hide or mute it.
 #[component]
 pub fn StoryCard(props: StoryCardProps) -> Element {
   let theme_signal = use_context::<Signal<Theme>>();
   let theme = theme_signal.read();
+  let mut router = use_router();
+  let read_stories = use_context::<Signal<ReadStories>>();
+  let mut show_mute_menu = use_signal(|| false);
+  let highlights = use_context::<Signal<Highlights>>();
 
   const CARD_MARGIN: &str = "0 0 8 0";
   const CARD_CORNER_RADIUS: &str = "8";
@@ -20,6 +36,17 @@ pub fn StoryCard(props: StoryCardProps) -> Element {
   const CARD_PADDING: &str = "12 16";
 
   let story_id = props.story.id;
+  let title_color = if read_stories.read().is_read(story_id) { theme.color.text_alt } else { theme.color.base };
+  let title = props.story.title.as_deref().unwrap_or("[No Title]");
+  // The title split into the runs the highlight rules color differently.
+  let title_runs: Vec<(String, &str)> = highlights
+    .read()
+    .runs(title)
+    .into_iter()
+    .map(|(run, color)| (run.to_string(), color.map_or(title_color, |c| theme.color.highlight.get(c))))
+    .collect();
+  let new_comments = read_stories.read().new_comment_count(&props.story);
+  let border = if props.is_focused { format!("2 inner {}", theme.color.focus_ring) } else { "none".to_string() };
 
   rsx! {
       rect {
@@ -32,15 +59,47 @@ pub fn StoryCard(props: StoryCardProps) -> Element {
           corner_radius: CARD_CORNER_RADIUS,
           background: "{theme.color.background_card}",
           shadow: CARD_SHADOW,
+          border: "{border}",
           onclick: move |_| props.on_select.call(story_id),
+          onmiddleclick: move |_| router.open_in_background(story_id),
 
-          label {
-              font_family: "{theme.font.serif}",
-              font_size: "{theme.size.text_xl}",
-              font_weight: "{theme.font_weight.bold}",
-              color: "{theme.color.base}",
-              max_lines: "2",
-              "{props.story.title.as_deref().unwrap_or(\"[No Title]\")}"
+          rect {
+              width: "100%",
+              direction: "horizontal",
+              content: "flex",
+              paragraph {
+                  width: "flex(1)",
+                  max_lines: "2",
+                  for (run, color) in title_runs {
+                      text {
+                          font_family: "{theme.font.serif}",
+                          font_size: "{theme.size.text_xl}",
+                          font_weight: "{theme.font_weight.bold}",
+                          color: "{color}",
+                          "{run}"
+                      }
+                  }
+              }
+              BookmarkStar { item: SavedItem::Story(props.story.clone()) }
+              CursorArea {
+                  icon: CursorIcon::Pointer,
+                  rect {
+                      padding: "0 4",
+                      onclick: move |e: MouseEvent| {
+                          e.stop_propagation();
+                          show_mute_menu.toggle();
+                      },
+                      label {
+                          font_size: "{theme.size.text_xl}",
+                          color: "{theme.color.text_alt}",
+                          "⋯"
+                      }
+                  }
+              }
+          }
+
+          if *show_mute_menu.read() {
+              MuteMenu { story: props.story.clone(), on_close: move |_| show_mute_menu.set(false) }
           }
 
           {
@@ -56,7 +115,7 @@ pub fn StoryCard(props: StoryCardProps) -> Element {
               })
           }
 
-          CardFooter { story: props.story.clone() }
+          CardFooter { story: props.story.clone(), new_comments }
       }
   }
 }
@@ -1,16 +1,30 @@
+use crate::components::author_link::AuthorLink;
+use crate::components::bookmark_star::BookmarkStar;
 use crate::components::comment_view::CommentView;
 use crate::components::icons::*;
+use crate::components::indication_label::IndicationLabel;
 use crate::components::info_line::InfoLine;
-use crate::components::no_story_selected_view::NoStorySelectedView;
-use crate::components::primitives::Spacer;
+use crate::components::poll_options::PollOptions;
+use crate::components::primitives::{ErrorView, Spacer};
+use crate::components::rich_text::RichText;
+use crate::components::scroll_into_view::ScrollIntoView;
 use crate::components::skeletons::CommentSkeleton;
-use crate::models::{Comment, FetchState, Story};
+use crate::components::story_list_view::{LoadedStories, OpenedFrom};
+use crate::models::{Comment, FetchState, ItemKind, Story};
+use crate::router::{Route, use_router};
 use crate::theme::Theme;
 use crate::utils::api::ApiService;
+use crate::utils::bookmarks::SavedItem;
 use crate::utils::datetime::format_timestamp;
+use crate::utils::error::ApiError;
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use crate::utils::mutes::Mutes;
+use crate::utils::read_stories::{ReadStories, SeenComments};
+use crate::utils::reading_history::ReadingHistory;
 use freya::prelude::*;
-use log::info;
-use std::collections::HashMap;
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::sync::Arc;
 
 // --- Local Components ---
@@ -21,6 +35,9 @@ fn RenderComments(
   depth: u16,
   on_toggle_expand: EventHandler<u32>,
   on_retry_fetch: EventHandler<u32>,
+  focused_comment: Option<u32>,
+  new_comments: Memo<HashSet<u32>>,
+  scroll_controller: ScrollController,
 ) -> Element {
   let comments_map = all_comments.read();
   rsx! {
@@ -36,6 +53,9 @@ fn RenderComments(
                               depth: depth + 1,
                               on_toggle_expand: on_toggle_expand,
                               on_retry_fetch: on_retry_fetch,
+                              focused_comment: focused_comment,
+                              new_comments,
+                              scroll_controller: scroll_controller,
                           }
                       }
                   } else {
@@ -45,11 +65,17 @@ fn RenderComments(
                   rsx! {
                       Fragment {
                           key: "{comment.id}",
-                          CommentView {
-                              comment: comment.clone(),
-                              depth: depth,
-                              on_toggle_expand: on_toggle_expand,
-                              on_retry_fetch: on_retry_fetch,
+                          ScrollIntoView {
+                              active: focused_comment == Some(comment.id),
+                              scroll_controller: scroll_controller,
+                              CommentView {
+                                  comment: comment.clone(),
+                                  depth: depth,
+                                  on_toggle_expand: on_toggle_expand,
+                                  on_retry_fetch: on_retry_fetch,
+                                  is_focused: focused_comment == Some(comment.id),
+                                  is_new: new_comments.read().contains(&comment.id),
+                              }
                           }
                           {children_nodes}
                       }
@@ -62,12 +88,184 @@ fn RenderComments(
   }
 }
 
+// A small badge marking items that are not regular stories.
+#[component]
+fn KindBadge(text: &'static str) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      rect {
+          padding: "2 8",
+          margin: "0 0 6 0",
+          corner_radius: "6",
+          background: "{theme.color.accent}",
+          label {
+              font_family: "{theme.font.mono}",
+              font_size: "{theme.size.text_xs}",
+              font_weight: "{theme.font_weight.bold}",
+              color: "{theme.color.accent_text}",
+              "{text}"
+          }
+      }
+  }
+}
+
+// A button in the bar above the story; disabled buttons are dimmed and ignore clicks.
+#[component]
+fn NavButton(text: &'static str, #[props(default = true)] enabled: bool, onclick: EventHandler<()>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+
+  rsx! {
+      rect {
+          onclick: move |_| {
+              if enabled {
+                  onclick.call(());
+              }
+          },
+          background: "{theme.color.background_page}",
+          border: "1 solid {theme.color.border}",
+          padding: "6 10",
+          corner_radius: "6",
+          label {
+              font_family: "{theme.font.sans}",
+              font_size: "{theme.size.text_m}",
+              color: if enabled { "{theme.color.text}" } else { "{theme.color.text_alt}" },
+              opacity: if enabled { "1" } else { "0.5" },
+              "{text}"
+          }
+      }
+  }
+}
+
+// Progress of a request to open the next story before it has been loaded.
+#[derive(Clone, Copy, PartialEq)]
+enum AwaitingNext {
+  No,
+  Requested,
+  Loading,
+}
+
+// Fetches the replies of a comment into its `children` and the shared comment map,
+// tracking progress in `fetch_state`. Replies that no longer exist are skipped; any
+// other failure fails the whole thread.
+async fn fetch_replies(
+  api_service: Arc<ApiService>,
+  kids: Vec<u32>,
+  mut all_comments: Signal<HashMap<u32, Comment>>,
+  mut children: Signal<Vec<Comment>>,
+  mut fetch_state: Signal<FetchState>,
+) {
+  fetch_state.set(FetchState::Loading);
+  let mut fetched_children = Vec::new();
+  for kid_id in kids {
+    match api_service.fetch_comment_content(kid_id).await {
+      Ok(child) => fetched_children.push(child),
+      Err(ApiError::NotFound) => warn!("Skipping missing reply {kid_id}"),
+      Err(e) => {
+        fetch_state.set(FetchState::Failed(e));
+        return;
+      }
+    }
+  }
+  all_comments.write().extend(fetched_children.iter().map(|child| (child.id, child.clone())));
+  children.set(fetched_children);
+  fetch_state.set(FetchState::Idle);
+}
+
+// Expands or collapses a comment, loading its replies the first time it is expanded.
+fn toggle_comment(api_service: Arc<ApiService>, all_comments: Signal<HashMap<u32, Comment>>, comment_id: u32) {
+  let comments_map = all_comments.read();
+  let Some(comment) = comments_map.get(&comment_id) else { return };
+  let mut is_expanded = comment.is_expanded;
+  if *is_expanded.read() {
+    is_expanded.set(false);
+    return;
+  }
+  is_expanded.set(true);
+  if comment.children.read().is_empty()
+    && let Some(kids) = comment.kids.clone()
+  {
+    spawn(fetch_replies(api_service, kids, all_comments, comment.children, comment.fetch_state));
+  }
+}
+
+// The ids of the comments currently on screen, in display order.
+fn visible_comment_ids(ids: &[u32], comments_map: &HashMap<u32, Comment>) -> Vec<u32> {
+  comment_ids(ids, comments_map, true)
+}
+
+// The ids of the loaded comments in display order, with those in collapsed threads
+// unless `only_visible`.
+fn comment_ids(ids: &[u32], comments_map: &HashMap<u32, Comment>, only_visible: bool) -> Vec<u32> {
+  let mut found = Vec::new();
+  for id in ids {
+    let Some(comment) = comments_map.get(id) else { continue };
+    found.push(*id);
+    if (!only_visible || *comment.is_expanded.read()) && *comment.fetch_state.read() == FetchState::Idle {
+      let children_ids: Vec<u32> = comment.children.read().iter().map(|c| c.id).collect();
+      found.extend(comment_ids(&children_ids, comments_map, only_visible));
+    }
+  }
+  found
+}
+
+// The first new comment after the focused one in the loaded threads, collapsed or
+// not, wrapping around.
+fn next_new_comment(
+  kids: &[u32],
+  all_comments: &HashMap<u32, Comment>,
+  new_comments: &HashSet<u32>,
+  focused: Option<u32>,
+) -> Option<u32> {
+  let ids = comment_ids(kids, all_comments, false);
+  let start = focused.and_then(|id| ids.iter().position(|&v| v == id)).map_or(0, |p| p + 1);
+  ids[start..].iter().chain(&ids[..start]).copied().find(|id| new_comments.contains(id))
+}
+
+// Expands the threads a comment is in, so that it is on screen.
+fn reveal_comment(all_comments: &HashMap<u32, Comment>, id: u32) {
+  let mut parent = all_comments.get(&id).and_then(|comment| comment.parent);
+  while let Some(comment) = parent.and_then(|parent| all_comments.get(&parent)) {
+    let mut is_expanded = comment.is_expanded;
+    if !*is_expanded.peek() {
+      is_expanded.set(true);
+    }
+    parent = comment.parent;
+  }
+}
+
+// Focuses the next new comment, expanding the threads it is in.
+fn focus_next_new_comment(
+  kids: &[u32],
+  all_comments: Signal<HashMap<u32, Comment>>,
+  new_comments: &HashSet<u32>,
+  mut focused_comment: Signal<Option<u32>>,
+) {
+  let all_comments = all_comments.peek();
+  let focused = *focused_comment.peek();
+  if let Some(next) = next_new_comment(kids, &all_comments, new_comments, focused) {
+    reveal_comment(&all_comments, next);
+    focused_comment.set(Some(next));
+  }
+}
+
 // --- Main Component ---
+/// The story, job or poll with the given id and its comments. `selected_story`
+/// holds the item if the view navigating here already had it; otherwise it is
+/// fetched. Only the active view, i.e. the tab shown, handles keys.
 #[component]
-pub fn StoryDetailView(story_data: Signal<Option<Story>>, on_back: EventHandler<()>) -> Element {
+pub fn StoryDetailView(
+  id: u32,
+  selected_story: Signal<Option<Story>>,
+  is_active: bool,
+  on_loaded: EventHandler<Story>,
+) -> Element {
   let theme_signal = use_context::<Signal<Theme>>();
   let theme = theme_signal.read();
   let api_service = use_context::<Arc<ApiService>>();
+  let mut router = use_router();
 
   const SKELETON_COUNT: usize = 5;
   const DETAIL_PADDING: &str = "15";
@@ -75,22 +273,156 @@ pub fn StoryDetailView(story_data: Signal<Option<Story>>, on_back: EventHandler<
   const COMMENTS_SECTION_SPACER: &str = "20";
   const TITLE_PLACEHOLDER: &str = "[No Title]";
 
-  let mut all_comments: Signal<HashMap<u32, Comment>> = use_signal(HashMap::new);
+  let mut story_data = use_signal(|| selected_story.peek().clone().filter(|story| story.id == id));
+  let all_comments: Signal<HashMap<u32, Comment>> = use_signal(HashMap::new);
+  // Read before this visit adds to them.
+  let seen_before = use_hook(move || Rc::new(SeenComments::load(id)));
+  // The comments seen so far, this visit included, saved as more are shown.
+  let mut seen_now = use_signal(|| seen_before.as_ref().clone().unwrap_or_else(SeenComments::first_visit));
+  let new_comments = use_memo(move || match seen_before.as_ref() {
+    Some(seen) => all_comments.read().values().filter(|comment| seen.is_new(comment)).map(|c| c.id).collect(),
+    None => HashSet::new(),
+  });
+  let mut focused_comment: Signal<Option<u32>> = use_signal(|| None);
+  let scroll_controller = use_scroll_controller(ScrollConfig::default);
+  let key_actions = use_key_actions();
+  let loaded = use_context::<LoadedStories>();
+  let opened_from = use_context::<OpenedFrom>();
+  let mut awaiting_next = use_signal(|| AwaitingNext::No);
+
+  // Where this story is in the list it was opened from, as sorted and filtered, if
+  // it is in it.
+  let position_in = move |order: &[usize], stories: &[Story]| {
+    order.iter().position(|&index| stories.get(index).is_some_and(|story| story.id == id))
+  };
+  // Where this story is on the page it was opened from instead, if that is not the list.
+  let position_in_source = move |ids: &[u32]| ids.iter().position(|&other| other == id);
+  let (can_walk, has_previous, has_next) = match position_in_source(&opened_from.ids.read()) {
+    Some(position) => (true, position > 0, position + 1 < opened_from.ids.read().len()),
+    None => {
+      let position = position_in(&loaded.order.read(), &loaded.stories.read());
+      (
+        position.is_some(),
+        position.is_some_and(|p| p > 0),
+        position.is_some_and(|p| p + 1 < loaded.order.read().len() || *loaded.has_more.read()),
+      )
+    }
+  };
+
+  // Opens the story at `index` of the list in place of this one, so that going back
+  // returns to the list with that story selected.
+  let mut open_at = move |index: usize| {
+    let Some(story) = loaded.stories.peek().get(index).cloned() else { return };
+    let mut focused = loaded.focused;
+    focused.set(Some(index));
+    let id = story.id;
+    selected_story.set(Some(story));
+    router.replace(Route::Item(id));
+  };
+
+  // Walks to the next (or previous) story of the page this one was opened from,
+  // loading the next batch of the list when this is the last story loaded.
+  let walk = use_callback(move |forward: bool| {
+    let source = opened_from.ids.peek().clone();
+    if let Some(position) = position_in_source(&source) {
+      let target = if forward { source.get(position + 1) } else { position.checked_sub(1).map(|p| &source[p]) };
+      if let Some(&target) = target {
+        router.replace(Route::Item(target));
+      }
+      return;
+    }
+    let order = loaded.order.peek().clone();
+    let Some(position) = position_in(&order, &loaded.stories.peek()) else { return };
+    if !forward {
+      if position > 0 {
+        open_at(order[position - 1]);
+      }
+    } else if let Some(&next) = order.get(position + 1) {
+      open_at(next);
+    } else if *loaded.has_more.peek() && *awaiting_next.peek() == AwaitingNext::No {
+      awaiting_next.set(AwaitingNext::Requested);
+      loaded.load_more.call(());
+    }
+  });
+
+  use_effect(move || {
+    let awaiting = *awaiting_next.read();
+    if awaiting == AwaitingNext::No {
+      return;
+    }
+    let order = loaded.order.read().clone();
+    let is_loading = *loaded.is_loading_more.read();
+    match position_in(&order, &loaded.stories.peek()) {
+      Some(position) if position + 1 < order.len() => {
+        awaiting_next.set(AwaitingNext::No);
+        open_at(order[position + 1]);
+      }
+      _ if is_loading => awaiting_next.set(AwaitingNext::Loading),
+      // The batch finished loading without adding a story after this one.
+      _ if awaiting == AwaitingNext::Loading => awaiting_next.set(AwaitingNext::No),
+      _ => {}
+    }
+  });
+
+  use_effect(move || {
+    if let Some(story) = story_data.read().clone() {
+      on_loaded.call(story);
+    }
+    focused_comment.set(None);
+  });
+
+  // Stories count as read once shown, not when loaded into a background tab.
+  let mut read_stories = use_context::<Signal<ReadStories>>();
+  let mut reading_history = use_context::<Signal<ReadingHistory>>();
+  let mutes = use_context::<Signal<Mutes>>();
+  use_effect(use_reactive!(|is_active| {
+    if is_active && let Some(story) = story_data.read().as_ref() {
+      read_stories.write().mark_read(story);
+      reading_history.write().record(story.into());
+    }
+  }));
+
+  let mut story_resource = use_resource({
+    let api_service = api_service.clone();
+    move || {
+      let api_service = api_service.clone();
+      async move {
+        if story_data.peek().as_ref().is_some_and(|story| story.id == id) {
+          return Ok(());
+        }
+        info!("Fetching item {id} for the detail view");
+        let story = api_service.fetch_story_content(id).await?;
+        story_data.set(Some(story));
+        Ok::<(), ApiError>(())
+      }
+    }
+  });
 
   let comments_resource = use_resource({
     let api_service = api_service.clone();
     move || {
-      let story = story_data.read().clone();
+      let story = story_data.read().clone().filter(|story| story.id == id);
       let mut all_comments = all_comments;
+      let mut story_data = story_data;
       let api_service = api_service.clone();
       async move {
-        if let Some(story) = story {
-          if let Some(kids) = story.kids {
-            info!("Fetching {} top-level comments...", kids.len());
-            for kid_id in kids {
-              if let Ok(comment) = api_service.fetch_comment_content(kid_id).await {
-                all_comments.write().insert(kid_id, comment);
-              }
+        // Stories coming from search results carry no comment ids; load the full
+        // item, which re-runs this resource once it is stored.
+        if let Some(story) = &story
+          && story.kids.is_none()
+          && let Ok(full_story) = api_service.fetch_story_content(story.id).await
+          && full_story.kids.is_some()
+        {
+          story_data.set(Some(full_story));
+          return;
+        }
+        if let Some(story) = story
+          && let Some(kids) = story.kids
+        {
+          info!("Fetching {} top-level comments...", kids.len());
+          for kid_id in kids {
+            if let Ok(comment) = api_service.fetch_comment_content(kid_id).await {
+              all_comments.write().insert(kid_id, comment);
             }
           }
         }
@@ -98,13 +430,70 @@ pub fn StoryDetailView(story_data: Signal<Option<Story>>, on_back: EventHandler<
     }
   });
 
-  if let Some(story) = story_data.read().as_ref() {
+  // Comments count as seen once shown, the top-level ones after all have loaded.
+  use_effect(use_reactive!(|is_active| {
+    let comments_loaded = comments_resource.value().read().is_some();
+    if is_active && comments_loaded {
+      seen_now.write().record(id, all_comments.read().keys().copied());
+    }
+  }));
+
+  if let Some(story) = story_data.read().as_ref().filter(|story| story.id == id) {
+    let is_job = story.kind == ItemKind::Job;
+    let new_comment_count = new_comments.read().len();
+    let muted_comment_count = {
+      let mutes = mutes.read();
+      all_comments.read().values().filter(|comment| mutes.hides_comment(comment)).count()
+    };
+    let jump_to_new_comment = {
+      let kids = story.kids.clone().unwrap_or_default();
+      move |_| focus_next_new_comment(&kids, all_comments, &new_comments.read(), focused_comment)
+    };
+    let onglobalkeydown = {
+      let api_service = api_service.clone();
+      let kids = story.kids.clone().unwrap_or_default();
+      move |e: KeyboardEvent| {
+        if !is_active {
+          return;
+        }
+        let visible = visible_comment_ids(&kids, &all_comments.read());
+        let position = focused_comment.read().and_then(|id| visible.iter().position(|&v| v == id));
+        match key_actions.action(&e, Scope::Detail) {
+          Some(KeyAction::FocusNext) => {
+            if let Some(&id) = visible.get(position.map_or(0, |p| p + 1)) {
+              focused_comment.set(Some(id));
+            }
+          }
+          Some(KeyAction::FocusPrevious) => {
+            if let Some(&id) = visible.get(position.map_or(0, |p| p.saturating_sub(1))) {
+              focused_comment.set(Some(id));
+            }
+          }
+          Some(KeyAction::ToggleComment) => {
+            if let Some(id) = *focused_comment.read() {
+              toggle_comment(api_service.clone(), all_comments, id);
+            }
+          }
+          Some(KeyAction::NextNewComment) => {
+            focus_next_new_comment(&kids, all_comments, &new_comments.read(), focused_comment)
+          }
+          Some(KeyAction::NextStory) => walk.call(true),
+          Some(KeyAction::PreviousStory) => walk.call(false),
+          Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+          Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+          _ => {}
+        }
+      }
+    };
+
     rsx! {
         ScrollView {
+            scroll_controller,
             width: "100%",
             height: "fill",
             show_scrollbar: true,
             rect {
+                onglobalkeydown,
                 width: "100%",
                 height: "auto",
                 padding: DETAIL_PADDING,
@@ -113,25 +502,50 @@ pub fn StoryDetailView(story_data: Signal<Option<Story>>, on_back: EventHandler<
 
                 // Story Header
                 rect {
-                    onclick: move |_| on_back.call(()),
-                    background: "{theme.color.background_page}",
-                    border: "1 solid {theme.color.border}",
-                    padding: "6 10",
-                    corner_radius: "6",
-                    label {
-                        font_family: "{theme.font.sans}",
-                        font_size: "{theme.size.text_m}",
-                        color: "{theme.color.text}",
-                        "← Back to List"
+                    width: "100%",
+                    direction: "horizontal",
+                    content: "flex",
+                    cross_align: "center",
+                    NavButton { text: "← Back", onclick: move |_| router.back() }
+                    Spacer { width: "6" }
+                    BookmarkStar { item: SavedItem::Story(story.clone()) }
+                    rect { width: "flex(1)" }
+                    if can_walk {
+                        if *awaiting_next.read() != AwaitingNext::No {
+                            label {
+                                font_family: "{theme.font.sans}",
+                                font_size: "{theme.size.text_s}",
+                                color: "{theme.color.text_alt}",
+                                "Loading more stories..."
+                            }
+                            Spacer { width: "8" }
+                        }
+                        NavButton { text: "‹ Previous", enabled: has_previous, onclick: move |_| walk.call(false) }
+                        Spacer { width: "6" }
+                        NavButton { text: "Next ›", enabled: has_next, onclick: move |_| walk.call(true) }
                     }
                 }
                 Spacer { height: VERTICAL_SPACER_HEIGHT }
+                match story.kind {
+                    ItemKind::Job => rsx! { KindBadge { text: "JOB" } },
+                    ItemKind::Poll => rsx! { KindBadge { text: "POLL" } },
+                    _ => rsx! { Fragment {} },
+                }
                 label {
                     font_family: "{theme.font.serif}",
                     font_size: "{theme.size.text_xxl}",
                     font_weight: "{theme.font_weight.bold}",
+                    color: if story.dead || story.deleted { "{theme.color.text_alt}" } else { "{theme.color.text}" },
                     "{story.title.as_deref().unwrap_or(TITLE_PLACEHOLDER)}"
                 }
+                if story.dead || story.deleted {
+                    label {
+                        font_family: "{theme.font.sans}",
+                        font_size: "{theme.size.text_s}",
+                        color: "{theme.color.text_alt}",
+                        if story.deleted { "[deleted]" } else { "[dead]" }
+                    }
+                }
                 Spacer { height: VERTICAL_SPACER_HEIGHT }
                 if let Some(url) = &story.url {
                     Link {
@@ -149,123 +563,178 @@ pub fn StoryDetailView(story_data: Signal<Option<Story>>, on_back: EventHandler<
                 // Story Metadata
                 InfoLine { icon: rsx!{ IconScore {} }, text: format!("Score: {}", story.score.unwrap_or(0)) }
                 Spacer { height: VERTICAL_SPACER_HEIGHT }
-                InfoLine { icon: rsx!{ IconUser {} }, text: format!("By: {}", story.by.as_deref().unwrap_or("N/A")) }
+                AuthorLink {
+                    name: story.by.clone(),
+                    InfoLine { icon: rsx!{ IconUser {} }, text: format!("By: {}", story.by.as_deref().unwrap_or("N/A")) }
+                }
                 Spacer { height: VERTICAL_SPACER_HEIGHT }
                 if let Some(time) = &story.time {
                     InfoLine { icon: rsx!{ IconTime {} }, text: format!("Time: {}", format_timestamp(time)) }
                     Spacer { height: VERTICAL_SPACER_HEIGHT }
                 }
-                InfoLine { icon: rsx!{ IconComments {} }, text: format!("Comments: {}", story.descendants.unwrap_or(0)) }
-                Spacer { height: COMMENTS_SECTION_SPACER }
+                if !is_job {
+                    InfoLine { icon: rsx!{ IconComments {} }, text: format!("Comments: {}", story.descendants.unwrap_or(0)) }
+                }
 
-                // Comments Section
-                label {
-                    font_size: "{theme.size.text_l}",
-                    font_weight: "{theme.font_weight.bold}",
-                    "Comments:"
+                // Self-post body (Ask HN, Show HN, jobs and polls)
+                if let Some(text) = story.text.clone() {
+                    Spacer { height: COMMENTS_SECTION_SPACER }
+                    RichText { html: text }
+                }
+
+                if let Some(parts) = story.parts.clone() {
+                    Spacer { height: COMMENTS_SECTION_SPACER }
+                    PollOptions { parts }
                 }
-                Spacer { height: "4" }
+                Spacer { height: COMMENTS_SECTION_SPACER }
 
-                if comments_resource.value().read().is_none() {
-                    Fragment {
-                        {
-                            (0..SKELETON_COUNT).map(|_| rsx!{ CommentSkeleton {} })
+                // Comments Section
+                if is_job {
+                    label {
+                        color: "{theme.color.text_alt}",
+                        "Job postings are not open for discussion."
+                    }
+                } else {
+                    rect {
+                        width: "100%",
+                        direction: "horizontal",
+                        content: "flex",
+                        cross_align: "center",
+                        label {
+                            width: "flex(1)",
+                            font_size: "{theme.size.text_l}",
+                            font_weight: "{theme.font_weight.bold}",
+                            "Comments:"
+                        }
+                        if muted_comment_count > 0 {
+                            label {
+                                font_family: "{theme.font.sans}",
+                                font_size: "{theme.size.text_s}",
+                                color: "{theme.color.text_alt}",
+                                "{muted_comment_count} muted"
+                            }
+                            Spacer { width: "8" }
+                        }
+                        if new_comment_count > 0 {
+                            label {
+                                font_family: "{theme.font.sans}",
+                                font_size: "{theme.size.text_s}",
+                                color: "{theme.color.text_alt}",
+                                "{new_comment_count} new"
+                            }
+                            Spacer { width: "8" }
+                            NavButton { text: "Next new ↓", onclick: jump_to_new_comment }
                         }
                     }
-                } else if let Some(kids) = &story.kids {
-                    RenderComments {
-                        comment_ids: kids.clone(),
-                        all_comments: all_comments,
-                        depth: 0,
-                        on_toggle_expand: {
-                            let api_service = api_service.clone();
-                            move |comment_id: u32| {
-                                let mut comments_map = all_comments.write();
-                                if let Some(comment_to_toggle) = comments_map.get_mut(&comment_id) {
-                                    let is_currently_expanded = *comment_to_toggle.is_expanded.read();
-                                    if !is_currently_expanded {
-                                        comment_to_toggle.is_expanded.set(true);
-                                        if comment_to_toggle.children.read().is_empty() {
-                                            if let Some(kids) = comment_to_toggle.kids.clone() {
-                                                let mut children_signal = comment_to_toggle.children;
-                                                let mut fetch_state_signal = comment_to_toggle.fetch_state;
-                                                let api_service = api_service.clone();
-                                                spawn(async move {
-                                                    fetch_state_signal.set(FetchState::Loading);
-                                                    let mut fetched_children = Vec::new();
-                                                    let mut all_successful = true;
-                                                    for kid_id in kids {
-                                                        if let Ok(child) = api_service.fetch_comment_content(kid_id).await {
-                                                            fetched_children.push(child);
-                                                        } else {
-                                                            all_successful = false;
-                                                            break;
-                                                        }
-                                                    }
-                                                    if all_successful {
-                                                        children_signal.set(fetched_children);
-                                                        fetch_state_signal.set(FetchState::Idle);
-                                                    } else {
-                                                        fetch_state_signal.set(FetchState::Failed);
-                                                    }
-                                                });
-                                            }
-                                        }
-                                    } else {
-                                        comment_to_toggle.is_expanded.set(false);
-                                    }
-                                }
+                    Spacer { height: "4" }
+
+                    if comments_resource.value().read().is_none() {
+                        Fragment {
+                            {
+                                (0..SKELETON_COUNT).map(|_| rsx!{ CommentSkeleton {} })
                             }
-                        },
-                        on_retry_fetch: {
-                            let api_service = api_service.clone();
-                            move |comment_id: u32| {
-                                let mut comments_map = all_comments.write();
-                                if let Some(comment_to_toggle) = comments_map.get_mut(&comment_id) {
-                                    if *comment_to_toggle.fetch_state.read() == FetchState::Failed {
-                                        if let Some(kids) = comment_to_toggle.kids.clone() {
-                                            let mut children_signal = comment_to_toggle.children;
-                                            let mut fetch_state_signal = comment_to_toggle.fetch_state;
-                                            let api_service = api_service.clone();
-                                            spawn(async move {
-                                                fetch_state_signal.set(FetchState::Loading);
-                                                let mut fetched_children = Vec::new();
-                                                let mut all_successful = true;
-                                                for kid_id in kids {
-                                                    if let Ok(child) = api_service.fetch_comment_content(kid_id).await {
-                                                        fetched_children.push(child);
-                                                    } else {
-                                                        all_successful = false;
-                                                        break;
-                                                    }
-                                                }
-                                                if all_successful {
-                                                    children_signal.set(fetched_children);
-                                                    fetch_state_signal.set(FetchState::Idle);
-                                                } else {
-                                                    fetch_state_signal.set(FetchState::Failed);
-                                                }
-                                            });
-                                        }
+                        }
+                    } else if let Some(kids) = &story.kids {
+                        RenderComments {
+                            comment_ids: kids.clone(),
+                            all_comments: all_comments,
+                            depth: 0,
+                            focused_comment: *focused_comment.read(),
+                            new_comments,
+                            scroll_controller,
+                            on_toggle_expand: {
+                                let api_service = api_service.clone();
+                                move |comment_id: u32| toggle_comment(api_service.clone(), all_comments, comment_id)
+                            },
+                            on_retry_fetch: {
+                                let api_service = api_service.clone();
+                                move |comment_id: u32| {
+                                    let comments_map = all_comments.read();
+                                    if let Some(comment_to_toggle) = comments_map.get(&comment_id)
+                                        && matches!(*comment_to_toggle.fetch_state.read(), FetchState::Failed(_))
+                                        && let Some(kids) = comment_to_toggle.kids.clone()
+                                    {
+                                        spawn(fetch_replies(
+                                            api_service.clone(),
+                                            kids,
+                                            all_comments,
+                                            comment_to_toggle.children,
+                                            comment_to_toggle.fetch_state,
+                                        ));
                                     }
                                 }
-                            }
-                        },
-                    }
-                } else {
-                    label {
-                        color: "{theme.color.text_alt}",
-                        "No comments to display."
+                            },
+                        }
+                    } else {
+                        label {
+                            color: "{theme.color.text_alt}",
+                            "No comments to display."
+                        }
                     }
                 }
             }
         }
     }
   } else {
-    rsx! {
-        NoStorySelectedView {
-            on_back: on_back
-        }
+    match story_resource.value().read().as_ref() {
+      Some(Err(err)) => rsx! {
+          ErrorView { error: err.clone(), on_retry: move |_| story_resource.restart() }
+      },
+      _ => rsx! { IndicationLabel { text: "Loading story..." } },
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dioxus::dioxus_core::{ScopeId, VNode, VirtualDom};
+  use serde_json::json;
+
+  // Story 100 has the threads 1 → 2 → 3 and 4, with all replies loaded and every
+  // thread collapsed. Runs `test` within a scope, which the comment signals need.
+  fn with_thread(test: impl FnOnce(HashMap<u32, Comment>)) {
+    let dom = VirtualDom::new(VNode::empty);
+    dom.in_runtime(|| {
+      ScopeId::ROOT.in_runtime(|| {
+        let comment = |id: u32, parent: u32| -> Comment {
+          serde_json::from_value(json!({ "id": id, "type": "comment", "parent": parent, "time": 1 })).unwrap()
+        };
+        let comments = [comment(1, 100), comment(2, 1), comment(3, 2), comment(4, 100)];
+        let map: HashMap<u32, Comment> = comments.iter().map(|c| (c.id, c.clone())).collect();
+        for (parent, child) in [(1, 1), (2, 2)] {
+          let mut children = map[&parent].children;
+          children.set(vec![comments[child].clone()]);
+        }
+        test(map);
+      })
+    });
+  }
+
+  #[test]
+  fn collapsed_threads_are_not_visible() {
+    with_thread(|map| assert_eq!(visible_comment_ids(&[1, 4], &map), [1, 4]));
+  }
+
+  #[test]
+  fn new_comments_are_found_in_collapsed_threads() {
+    with_thread(|map| {
+      let new_comments = HashSet::from([3, 4]);
+      assert_eq!(next_new_comment(&[1, 4], &map, &new_comments, None), Some(3));
+      assert_eq!(next_new_comment(&[1, 4], &map, &new_comments, Some(3)), Some(4));
+      // Wraps around after the last one.
+      assert_eq!(next_new_comment(&[1, 4], &map, &new_comments, Some(4)), Some(3));
+      assert_eq!(next_new_comment(&[1, 4], &map, &HashSet::new(), None), None);
+    });
+  }
+
+  #[test]
+  fn revealing_a_comment_expands_its_threads() {
+    with_thread(|map| {
+      reveal_comment(&map, 3);
+      assert!(*map[&1].is_expanded.peek() && *map[&2].is_expanded.peek());
+      assert!(!*map[&3].is_expanded.peek() && !*map[&4].is_expanded.peek());
+      assert_eq!(visible_comment_ids(&[1, 4], &map), [1, 2, 3, 4]);
+    });
+  }
+}
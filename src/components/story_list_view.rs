@@ -1,21 +1,182 @@
-use crate::CurrentView;
+use crate::components::failed_story_card::FailedStoryCard;
 use crate::components::indication_label::IndicationLabel;
 use crate::components::primitives::ErrorView;
+use crate::components::scroll_into_view::ScrollIntoView;
 use crate::components::story_card::StoryCard;
-use crate::models::Story;
+use crate::models::{FailedStory, Story};
+use crate::router::{Route, use_list_scroll_restoration, use_router};
+use crate::theme::Theme;
+use crate::utils::api::ApiService;
+use crate::utils::error::ApiError;
+use crate::utils::highlights::Highlights;
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use crate::utils::list_filter::ListFilter;
+use crate::utils::read_stories::ReadStories;
 use freya::prelude::*;
+use std::sync::Arc;
 
+/// The stories loaded into the current list, shared through context so that the
+/// detail view can walk to the next or previous one.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LoadedStories {
+  pub stories: Signal<Vec<Story>>,
+  /// Indices into `stories` of the ones the sort, filters and mutes show, in their order.
+  pub order: Memo<Vec<usize>>,
+  /// Index into `stories` of the story the list's focus cursor is on.
+  pub focused: Signal<Option<usize>>,
+  /// Whether the list has more stories than are loaded.
+  pub has_more: Memo<bool>,
+  pub is_loading_more: Signal<bool>,
+  /// Loads the next batch of stories, if there is one and none is loading.
+  pub load_more: Callback<()>,
+}
+
+/// The ids, in display order, of the stories on the page a story was opened from
+/// when that is not the story list: the saved items, the reading history or a
+/// profile. Shared through context so that the detail view walks through those
+/// instead of the loaded stories; empty when the story list opened it.
+#[derive(Clone, Copy, PartialEq)]
+pub struct OpenedFrom {
+  pub ids: Signal<Vec<u32>>,
+}
+
+// Fetches a story that failed to load before, moving it from the placeholders into
+// the list on success.
+fn retry_story(
+  api_service: Arc<ApiService>,
+  id: u32,
+  mut stories_signal: Signal<Vec<Story>>,
+  mut failed_stories: Signal<Vec<FailedStory>>,
+) {
+  spawn(async move {
+    let result = api_service.fetch_story_content(id).await;
+    let mut failed = failed_stories.write();
+    match result {
+      Ok(story) => {
+        failed.retain(|f| f.id != id);
+        stories_signal.write().push(story);
+      }
+      Err(error) => {
+        if let Some(failure) = failed.iter_mut().find(|f| f.id == id) {
+          failure.error = error;
+        }
+      }
+    }
+  });
+}
+
+// Whether the story at `index` is left out of the list, on top of the muted ones
+// `story_order` already leaves out. When only highlighted stories are shown, the
+// others always are; read ones unless focused, so that a story does not disappear
+// right after it was read.
+fn is_hidden(
+  stories: &[Story],
+  index: usize,
+  focused: Option<usize>,
+  read_stories: &ReadStories,
+  highlights: &Highlights,
+) -> bool {
+  stories
+    .get(index)
+    .is_some_and(|story| highlights.hides(story) || focused != Some(index) && read_stories.hides(story.id))
+}
+
+// --- Local Components ---
+#[component]
+fn StoryCards(
+  stories_signal: Signal<Vec<Story>>,
+  story_order: Memo<Vec<usize>>,
+  failed_stories: Signal<Vec<FailedStory>>,
+  selected_story_data: Signal<Option<Story>>,
+  focused_story: Signal<Option<usize>>,
+  scroll_controller: ScrollController,
+) -> Element {
+  let router = use_router();
+  let api_service = use_context::<Arc<ApiService>>();
+  let read_stories = use_context::<Signal<ReadStories>>();
+  let highlights = use_context::<Signal<Highlights>>();
+  let opened_from = use_context::<OpenedFrom>();
+  let stories = stories_signal.read();
+  let failed = failed_stories.read();
+  let focused = *focused_story.read();
+  let read_stories = read_stories.read();
+  let highlights = highlights.read();
+  let shown = story_order
+    .read()
+    .iter()
+    .copied()
+    .filter(|&index| !is_hidden(&stories, index, focused, &read_stories, &highlights))
+    .map(|index| (index, &stories[index]))
+    .collect::<Vec<_>>();
+
+  rsx! {
+      for (index, story_item) in shown {
+          ScrollIntoView {
+              key: "{story_item.id}",
+              active: focused == Some(index),
+              scroll_controller,
+              StoryCard {
+                  story: story_item.clone(),
+                  is_focused: focused == Some(index),
+                  // The on_select handler now receives the story's ID.
+                  // This is a more flexible pattern than passing the whole story.
+                  on_select: {
+                      // Clone the story list to be used inside the closure.
+                      let stories = stories_signal.read().clone();
+                      let mut selected_story_data = selected_story_data;
+                      let mut router = router;
+                      let mut focused_story = focused_story;
+                      let mut opened_from = opened_from;
+                      move |id: u32| {
+                          // Find the full story object using the received ID.
+                          if let Some(index) = stories.iter().position(|s| s.id == id) {
+                              focused_story.set(Some(index));
+                              opened_from.ids.set(Vec::new());
+                              selected_story_data.set(Some(stories[index].clone()));
+                              router.push(Route::Item(id));
+                          }
+                      }
+                  }
+              }
+          }
+      }
+      for failure in failed.iter() {
+          FailedStoryCard {
+              failure: failure.clone(),
+              on_retry: {
+                  let api_service = api_service.clone();
+                  move |id: u32| retry_story(api_service.clone(), id, stories_signal, failed_stories)
+              }
+          }
+      }
+  }
+}
+
+// --- Main Component ---
 #[component]
 pub fn StoryListView(
   stories_signal: Signal<Vec<Story>>,
-  error_signal: Signal<Option<String>>,
-  best_story_ids_resource: Resource<Result<Vec<u32>, String>>,
+  story_order: Memo<Vec<usize>>,
+  list_filter: Signal<ListFilter>,
+  failed_stories: Signal<Vec<FailedStory>>,
+  best_story_ids_resource: Resource<Result<Vec<u32>, ApiError>>,
+  search_resource: Resource<Result<(), ApiError>>,
+  is_searching: bool,
   loaded_count: Signal<usize>,
   is_loading_more: Signal<bool>,
-  current_view: Signal<CurrentView>,
-  selected_story_data: Signal<Option<Story>>,
+  mut selected_story_data: Signal<Option<Story>>,
+  mut focused_story: Signal<Option<usize>>,
   scroll_controller: ScrollController,
 ) -> Element {
+  let key_actions = use_key_actions();
+  let mut router = use_router();
+  let read_stories = use_context::<Signal<ReadStories>>();
+  let highlights = use_context::<Signal<Highlights>>();
+  let loaded = use_context::<LoadedStories>();
+  let mut opened_from = use_context::<OpenedFrom>();
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  use_list_scroll_restoration(scroll_controller);
   // --- Constants ---
   const CONTAINER_WIDTH: &str = "100%";
   const SCROLL_HEIGHT: &str = "fill";
@@ -23,75 +184,159 @@ pub fn StoryListView(
   const FETCHING_TEXT: &str = "Fetching story details...";
   const LOADING_MORE_TEXT: &str = "Loading more stories...";
   const NO_STORIES_TEXT: &str = "No stories found.";
+  const SEARCHING_TEXT: &str = "Searching...";
+  const NO_RESULTS_TEXT: &str = "No results.";
+
+  let is_empty = stories_signal.read().is_empty() && failed_stories.read().is_empty();
+  // The filters may leave too few stories to scroll to the end and load more. The
+  // muted ones are counted in the header already.
+  let filtered_out = {
+    let stories = stories_signal.read();
+    stories.len() - list_filter.read().order(&stories).len()
+  };
+  let cards = rsx! {
+      StoryCards { stories_signal, story_order, failed_stories, selected_story_data, focused_story, scroll_controller }
+      if filtered_out > 0 && !*is_loading_more.read() {
+          rect {
+              width: "100%",
+              direction: "horizontal",
+              main_align: "center",
+              cross_align: "center",
+              padding: "10",
+              label {
+                  margin: "0 8 0 0",
+                  font_family: "{theme.font.sans}",
+                  font_size: "{theme.size.text_s}",
+                  color: "{theme.color.text_alt}",
+                  "{filtered_out} loaded stories left out by the filters"
+              }
+              if *loaded.has_more.read() {
+                  Button {
+                      onclick: move |_| loaded.load_more.call(()),
+                      label { "Load more" }
+                  }
+              }
+          }
+      }
+      // Show a loading indicator at the bottom during infinite scroll.
+      if *is_loading_more.read() && !is_empty {
+          IndicationLabel {
+              text: LOADING_MORE_TEXT.to_string(),
+          }
+      }
+  };
 
   // --- Render Logic ---
   // This block determines what content to show based on the current state
-  // of data fetching, prioritizing errors first.
-  let content = if let Some(err) = error_signal.read().as_ref() {
-    // State 1: A top-level error occurred during story fetching.
-    rsx! { ErrorView { message: err.clone() } }
+  // of data fetching. Stories that fail individually are shown as placeholder
+  // cards below the ones that loaded.
+  let content = if is_searching {
+    let retry_search = move |_| {
+      let mut search_resource = search_resource;
+      search_resource.restart();
+    };
+    match search_resource.value().read().as_ref() {
+      // A failed page keeps the results loaded so far above the error.
+      Some(Err(err)) => rsx! {
+          {cards}
+          ErrorView { error: err.clone(), on_retry: retry_search }
+      },
+      Some(Ok(())) if is_empty => rsx! { IndicationLabel { text: NO_RESULTS_TEXT.to_string() } },
+      _ if is_empty => rsx! { IndicationLabel { text: SEARCHING_TEXT.to_string() } },
+      _ => cards,
+    }
   } else {
     match best_story_ids_resource.value().read().as_ref() {
-      // State 2: The initial list of story IDs is still loading.
+      // State 1: The initial list of story IDs is still loading.
       None => rsx! { IndicationLabel { text: LOADING_TEXT.to_string() } },
 
-      // State 3: Fetching the list of story IDs failed.
-      Some(Err(err)) => rsx! { ErrorView { message: err.clone() } },
+      // State 2: Fetching the list of story IDs failed.
+      Some(Err(err)) => rsx! {
+          ErrorView {
+              error: err.clone(),
+              on_retry: move |_| {
+                  let mut best_story_ids_resource = best_story_ids_resource;
+                  best_story_ids_resource.restart();
+              }
+          }
+      },
 
-      // State 4: The API returned an empty list of stories.
+      // State 3: The API returned an empty list of stories.
       Some(Ok(ids)) if ids.is_empty() => {
         rsx! { IndicationLabel { text: NO_STORIES_TEXT.to_string() } }
       }
 
-      // State 5: We have story IDs, now render the list or a loading state.
-      Some(Ok(_)) => {
-        let stories = stories_signal.read();
-        if stories.is_empty() && !*is_loading_more.read() {
-          // The story list is empty, but we expect stories to be loading.
-          rsx! { IndicationLabel { text: FETCHING_TEXT.to_string() } }
-        } else {
-          // We have stories to display.
-          rsx! {
-              for story_item in stories.iter() {
-                  StoryCard {
-                      story: story_item.clone(),
-                      // The on_select handler now receives the story's ID.
-                      // This is a more flexible pattern than passing the whole story.
-                      on_select: {
-                          // Clone the story list to be used inside the closure.
-                          let stories = stories_signal.read().clone();
-                          let mut selected_story_data = selected_story_data;
-                          let mut current_view = current_view;
-                          move |id: u32| {
-                              // Find the full story object using the received ID.
-                              if let Some(story) = stories.iter().find(|s| s.id == id) {
-                                  selected_story_data.set(Some(story.clone()));
-                                  current_view.set(CurrentView::Detail);
-                              }
-                          }
-                      }
-                  }
-              }
-              // Show a loading indicator at the bottom during infinite scroll.
-              if *is_loading_more.read() {
-                  IndicationLabel {
-                      text: LOADING_MORE_TEXT.to_string(),
-                  }
-              }
-          }
+      // State 4: We have story IDs, but the first batch is still loading.
+      Some(Ok(_)) if is_empty => {
+        rsx! { IndicationLabel { text: FETCHING_TEXT.to_string() } }
+      }
+
+      // State 5: We have stories to display.
+      Some(Ok(_)) => cards,
+    }
+  };
+
+  let onglobalkeydown = move |e: KeyboardEvent| {
+    // While a page opened from the list is shown next to it, keys go to that page.
+    if !router.route().is_list() {
+      return;
+    }
+    let focused = *focused_story.read();
+    // The cursor follows the sort order and skips the stories left out of the list.
+    let shown: Vec<usize> = {
+      let stories = stories_signal.read();
+      let read_stories = read_stories.read();
+      let highlights = highlights.read();
+      story_order
+        .read()
+        .iter()
+        .copied()
+        .filter(|&index| !is_hidden(&stories, index, focused, &read_stories, &highlights))
+        .collect()
+    };
+    let position = focused.and_then(|focused| shown.iter().position(|&index| index == focused));
+    match key_actions.action(&e, Scope::List) {
+      Some(KeyAction::FocusNext) => {
+        let next = match position {
+          Some(position) => shown.get(position + 1).copied().or(focused),
+          None => shown.first().copied(),
+        };
+        focused_story.set(next);
+      }
+      Some(KeyAction::FocusPrevious) => {
+        let previous = match position {
+          Some(position) => position.checked_sub(1).map(|p| shown[p]).or(focused),
+          None => shown.first().copied(),
+        };
+        focused_story.set(previous);
+      }
+      Some(KeyAction::OpenStory) => {
+        if let Some(story) = focused.and_then(|i| stories_signal.read().get(i).cloned()) {
+          let id = story.id;
+          opened_from.ids.set(Vec::new());
+          selected_story_data.set(Some(story));
+          router.push(Route::Item(id));
         }
       }
+      Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+      Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+      _ => {}
     }
   };
 
   // The root element is a ScrollView that renders the determined content.
   rsx! {
-      ScrollView {
-          scroll_controller,
+      rect {
           width: CONTAINER_WIDTH,
           height: SCROLL_HEIGHT,
-          show_scrollbar: true,
-          {content}
+          onglobalkeydown,
+          ScrollView {
+              scroll_controller,
+              width: CONTAINER_WIDTH,
+              height: SCROLL_HEIGHT,
+              show_scrollbar: true,
+              {content}
+          }
       }
   }
 }
@@ -0,0 +1,226 @@
+use crate::components::comment_card::CommentCard;
+use crate::components::icons::*;
+use crate::components::indication_label::IndicationLabel;
+use crate::components::info_line::InfoLine;
+use crate::components::primitives::{ErrorView, Spacer};
+use crate::components::rich_text::RichText;
+use crate::components::story_card::StoryCard;
+use crate::components::story_list_view::OpenedFrom;
+use crate::models::{Item, Story};
+use crate::router::{Route, use_router, use_scroll_restoration};
+use crate::theme::Theme;
+use crate::utils::api::ApiService;
+use crate::utils::datetime::format_timestamp;
+use crate::utils::keys::{self, KeyAction, Scope, use_key_actions};
+use crate::utils::reading_history::{ReadingHistory, Visited};
+use freya::prelude::*;
+use futures::stream::{self, StreamExt};
+use log::info;
+use std::sync::Arc;
+
+const PAGE_SIZE: usize = 20;
+const SCROLL_END_MARGIN: i32 = 150;
+
+// The ids of the stories, jobs and polls among `items`.
+fn story_ids(items: &[Item]) -> Vec<u32> {
+  items
+    .iter()
+    .filter_map(|item| match item {
+      Item::Story(story) | Item::Job(story) | Item::Poll(story) => Some(story.id),
+      Item::Comment(_) | Item::PollOpt(_) | Item::Other => None,
+    })
+    .collect()
+}
+
+// --- Main Component ---
+/// A user's profile: karma, account age, the "about" text and their submissions.
+#[component]
+pub fn UserView(username: ReadOnlySignal<String>, selected_story_data: Signal<Option<Story>>) -> Element {
+  let theme_signal = use_context::<Signal<Theme>>();
+  let theme = theme_signal.read();
+  let api_service = use_context::<Arc<ApiService>>();
+  let mut router = use_router();
+
+  const DETAIL_PADDING: &str = "15";
+  const VERTICAL_SPACER_HEIGHT: &str = "12";
+  const SECTION_SPACER: &str = "20";
+
+  let mut opened_from = use_context::<OpenedFrom>();
+  let mut submissions: Signal<Vec<Item>> = use_signal(Vec::new);
+  // How many of the `submitted` ids should be loaded, and how many have been.
+  let mut requested_count = use_signal(|| PAGE_SIZE);
+  let mut fetched_count = use_signal(|| 0);
+  let mut is_loading = use_signal(|| false);
+  let scroll_controller = use_scroll_controller(ScrollConfig::default);
+  use_scroll_restoration(scroll_controller);
+  let key_actions = use_key_actions();
+
+  let mut user_resource = use_resource({
+    let api_service = api_service.clone();
+    move || {
+      let name = username.read().clone();
+      let api_service = api_service.clone();
+      async move {
+        info!("Fetching user profile: {name}");
+        api_service.fetch_user(&name).await
+      }
+    }
+  });
+
+  let mut reading_history = use_context::<Signal<ReadingHistory>>();
+  use_effect(move || {
+    if let Some(Ok(user)) = user_resource.value().read().as_ref() {
+      reading_history.write().record(Visited::User(user.id.clone()));
+    }
+  });
+
+  use_effect(move || {
+    username.read();
+    submissions.set(vec![]);
+    requested_count.set(PAGE_SIZE);
+    fetched_count.set(0);
+  });
+
+  let _ = use_resource({
+    let api_service = api_service.clone();
+    move || {
+      let submitted = user_resource.value().read().as_ref().and_then(|r| r.as_ref().ok()).map(|u| u.submitted.clone());
+      let requested = *requested_count.read();
+      let already_fetched = *fetched_count.peek();
+      let api_service = api_service.clone();
+      async move {
+        let Some(ids) = submitted else { return };
+        if already_fetched >= requested.min(ids.len()) {
+          return;
+        }
+        is_loading.set(true);
+        let items_futures = ids.iter().skip(already_fetched).take(requested - already_fetched).map(|&id| {
+          let api_service = api_service.clone();
+          async move { api_service.fetch_item::<Item>(id).await }
+        });
+        // `buffered` keeps the newest-first order of `submitted`.
+        let results = stream::iter(items_futures).buffered(10).collect::<Vec<_>>().await;
+        let items = results.into_iter().filter_map(Result::ok).filter(|item| match item {
+          Item::Story(_) | Item::Job(_) | Item::Poll(_) => true,
+          Item::Comment(comment) => !comment.deleted,
+          Item::PollOpt(_) | Item::Other => false,
+        });
+        submissions.write().extend(items);
+        fetched_count.set(requested.min(ids.len()));
+        is_loading.set(false);
+      }
+    }
+  });
+
+  // Loads the next page of submissions once scrolled near the end, or while they do
+  // not fill the view yet.
+  use_effect(move || {
+    let submitted_count =
+      user_resource.value().read().as_ref().and_then(|r| r.as_ref().ok()).map_or(0, |user| user.submitted.len());
+    let y_val = *scroll_controller.y().read();
+    let layout = scroll_controller.layout();
+    let layout_val = layout.read();
+    let end = layout_val.inner.height - layout_val.area.height();
+    let near_end = layout_val.inner.height <= layout_val.area.height() || -y_val > end as i32 - SCROLL_END_MARGIN;
+    let requested = *requested_count.peek();
+    if near_end && !*is_loading.read() && *fetched_count.read() >= requested && requested < submitted_count {
+      requested_count += PAGE_SIZE;
+    }
+  });
+
+  let user = match user_resource.value().read().as_ref() {
+    None => return rsx! { IndicationLabel { text: "Loading profile..." } },
+    Some(Err(err)) => {
+      return rsx! {
+          ErrorView { error: err.clone(), on_retry: move |_| user_resource.restart() }
+      };
+    }
+    Some(Ok(user)) => user.clone(),
+  };
+
+  rsx! {
+      ScrollView {
+          scroll_controller,
+          width: "100%",
+          height: "fill",
+          show_scrollbar: true,
+          rect {
+              onglobalkeydown: move |e: KeyboardEvent| match key_actions.action(&e, Scope::Detail) {
+                  Some(KeyAction::PageDown) => keys::scroll_by_pages(scroll_controller, 1),
+                  Some(KeyAction::PageUp) => keys::scroll_by_pages(scroll_controller, -1),
+                  _ => {}
+              },
+              width: "100%",
+              height: "auto",
+              padding: DETAIL_PADDING,
+              direction: "vertical",
+              background: "{theme.color.background_card}",
+
+              rect {
+                  onclick: move |_| router.back(),
+                  background: "{theme.color.background_page}",
+                  border: "1 solid {theme.color.border}",
+                  padding: "6 10",
+                  corner_radius: "6",
+                  label {
+                      font_family: "{theme.font.sans}",
+                      font_size: "{theme.size.text_m}",
+                      color: "{theme.color.text}",
+                      "← Back"
+                  }
+              }
+              Spacer { height: VERTICAL_SPACER_HEIGHT }
+              label {
+                  font_family: "{theme.font.serif}",
+                  font_size: "{theme.size.text_xxl}",
+                  font_weight: "{theme.font_weight.bold}",
+                  "{user.id}"
+              }
+              Spacer { height: VERTICAL_SPACER_HEIGHT }
+              InfoLine { icon: rsx!{ IconScore {} }, text: format!("Karma: {}", user.karma) }
+              Spacer { height: VERTICAL_SPACER_HEIGHT }
+              if let Some(created) = &user.created {
+                  InfoLine { icon: rsx!{ IconTime {} }, text: format!("Joined: {}", format_timestamp(created)) }
+                  Spacer { height: VERTICAL_SPACER_HEIGHT }
+              }
+              if let Some(about) = user.about.clone() {
+                  RichText { html: about }
+              }
+              Spacer { height: SECTION_SPACER }
+
+              label {
+                  font_size: "{theme.size.text_l}",
+                  font_weight: "{theme.font_weight.bold}",
+                  "Submissions ({user.submitted.len()}):"
+              }
+              Spacer { height: "4" }
+              for item in submissions.read().iter() {
+                  match item {
+                      Item::Story(story) | Item::Job(story) | Item::Poll(story) => rsx! {
+                          StoryCard {
+                              key: "{story.id}",
+                              story: story.clone(),
+                              on_select: {
+                                  let story = story.clone();
+                                  move |_| {
+                                      // The detail view walks through the submissions loaded so far.
+                                      opened_from.ids.set(story_ids(&submissions.peek()));
+                                      selected_story_data.set(Some(story.clone()));
+                                      router.push(Route::Item(story.id));
+                                  }
+                              }
+                          }
+                      },
+                      Item::Comment(comment) => rsx! {
+                          CommentCard { key: "{comment.id}", text: comment.text.clone(), time: comment.time }
+                      },
+                      Item::PollOpt(_) | Item::Other => rsx! { Fragment {} },
+                  }
+              }
+              if *is_loading.read() {
+                  IndicationLabel { text: "Loading submissions..." }
+              }
+          }
+      }
+  }
+}
@@ -1,57 +1,109 @@
 #![cfg_attr(all(not(debug_assertions), target_os = "windows"), windows_subsystem = "windows")]
 
 use freya::prelude::*;
-use futures::stream::{self, StreamExt};
-use log::{error, info};
+use log::info;
 use std::sync::Arc;
+use std::time::Duration;
 
 // --- Module Declarations ---
 mod components;
 mod models;
+mod router;
 mod theme;
 mod utils;
 
 // --- Imports ---
+use components::command_palette::Command;
+use components::story_list_view::{LoadedStories, OpenedFrom};
 use components::{
-  StoryDetailView, StoryListView, StoryTab,
-  icons::{IconMoon, IconSun},
+  CommandPalette, HighlightsView, HistoryView, ItemTabs, KeymapIssuesBanner, ListFilterBar, MutesView, OfflineBadge,
+  SavedView, SearchBar, ShownPanes, SplitPane, StoryListView, StoryTab, UserView,
+  icons::{IconBack, IconForward, IconMoon, IconSearch, IconSun},
   primitives::{IconButton, Spacer},
 };
 use freya::prelude::{ScrollDirection, ScrollPosition};
-use models::Story;
+use models::{FailedStory, Story};
+use router::{Route, use_router_provider};
 use theme::{Theme, ThemeMode};
 use utils::api::{ApiService, StoryListType};
+use utils::bookmarks::Bookmarks;
+use utils::highlights::Highlights;
+use utils::keymap::Keymap;
+use utils::keys::{KeyAction, Scope, use_key_actions};
+use utils::list_cache::ListCache;
+use utils::list_filter::ListFilter;
+use utils::mutes::Mutes;
+use utils::read_stories::ReadStories;
+use utils::reading_history::ReadingHistory;
+use utils::search::{SearchQuery, SearchService};
+use utils::store;
 
 // --- Application Constants ---
 const BATCH_SIZE: usize = 20;
 const SCROLL_END_MARGIN: i32 = 150;
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(30);
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
-
-// --- Top-level Application State ---
-#[derive(Clone, PartialEq)]
-enum CurrentView {
-  List,
-  Detail,
-}
+// From this window width on, stories and profiles open next to the list.
+const SPLIT_MIN_WIDTH: f32 = 900.0;
 
 // --- Main App Component ---
 fn app() -> Element {
   // --- State Signals ---
   let mut stories_signal: Signal<Vec<Story>> = use_signal(Vec::new);
-  let mut error_signal: Signal<Option<String>> = use_signal(|| None);
-  let mut current_view = use_signal(|| CurrentView::List);
-  let selected_story_data: Signal<Option<Story>> = use_signal(|| None);
+  let mut failed_stories: Signal<Vec<FailedStory>> = use_signal(Vec::new);
+  // Shared so that any view can navigate, e.g. author names to the profile view.
+  let mut router = use_router_provider(|| Route::List(StoryListType::Best));
+  let mut selected_story_data: Signal<Option<Story>> = use_signal(|| None);
   let mut loaded_count: Signal<usize> = use_signal(|| BATCH_SIZE);
   let mut is_loading_more: Signal<bool> = use_signal(|| false);
+  // The list shown by the last list route, kept while a story or profile is shown
+  // on top of it so that going back does not reload it.
   let mut current_list_type = use_signal(|| StoryListType::Best);
   let mut theme_mode = use_signal(|| ThemeMode::Light);
+  let is_offline = use_signal_sync(|| false);
+  let mut show_search = use_signal(|| false);
+  let mut search_query: Signal<Option<SearchQuery>> = use_signal(|| None);
+  let mut search_page: Signal<usize> = use_signal(|| 0);
+  let mut search_has_more: Signal<bool> = use_signal(|| false);
+  // How the loaded stories are sorted and filtered, kept across lists and searches.
+  let list_filter = use_signal(ListFilter::default);
+  // Index into `stories_signal` of the story the keyboard focus cursor is on.
+  let mut focused_story: Signal<Option<usize>> = use_signal(|| None);
+  // Every tab's list as it was left, so that switching tabs does not refetch them.
+  let mut list_cache = use_signal(ListCache::from_env);
+  // The tab whose list `stories_signal` holds; `None` while it holds search results.
+  let mut shown_list: Signal<Option<StoryListType>> = use_signal(|| None);
+  let keymap = use_signal(Keymap::load);
+  let mut read_stories = use_signal(ReadStories::load);
+  let bookmarks = use_signal(Bookmarks::load);
+  let reading_history = use_signal(ReadingHistory::load);
+  let mutes = use_signal(Mutes::load);
+  // The loaded stories to list, as sorted and filtered, without the muted ones.
+  let story_order = use_memo(move || {
+    let stories = stories_signal.read();
+    let mutes = mutes.read();
+    let order = list_filter.read().order(&stories);
+    order.into_iter().filter(|&index| !mutes.hides_story(&stories[index])).collect::<Vec<_>>()
+  });
+  let mut highlights = use_signal(Highlights::load);
+  let mut show_keymap_issues = use_signal(|| !keymap.peek().issues().is_empty());
+  let mut show_palette = use_signal(|| false);
+  // Ids of the palette commands run this session, most recent first.
+  let palette_recent: Signal<Vec<String>> = use_signal(Vec::new);
 
   // --- Service and Theme Instantiation and Context ---
-  let api_service = Arc::new(ApiService::new());
+  let api_service = use_hook(|| Arc::new(ApiService::from_env(is_offline)));
   use_context_provider(|| api_service.clone());
+  let search_service = use_hook(SearchService::from_env);
 
   let mut theme_signal = use_signal(Theme::light);
   use_context_provider(|| theme_signal);
+  use_context_provider(|| keymap);
+  use_context_provider(|| read_stories);
+  use_context_provider(|| bookmarks);
+  use_context_provider(|| reading_history);
+  use_context_provider(|| mutes);
+  use_context_provider(|| highlights);
 
   // This makes the `theme_mode` signal available to all children.
   use_context_provider(|| theme_mode);
@@ -65,13 +117,73 @@ fn app() -> Element {
   // --- Hooks ---
   let mut scroll_controller = use_scroll_controller(ScrollConfig::default);
 
+  let (root_reference, root_layout) = use_node_signal();
+  let is_wide = use_memo(move || root_layout.read().area.width() >= SPLIT_MIN_WIDTH);
+
+  // Switches to another search, or out of searching, starting over at its first
+  // page in the same update so that the results are never fetched with a stale page.
+  let mut set_search_query = move |query: Option<SearchQuery>| {
+    search_page.set(0);
+    search_has_more.set(false);
+    search_query.set(query);
+  };
+
+  // Follows the list or search the current page belongs to.
+  use_effect(move || match router.list_route() {
+    Some(Route::Saved | Route::History) if search_query.peek().is_some() => {
+      show_search.set(false);
+      set_search_query(None);
+    }
+    Some(Route::List(list_type)) => {
+      if search_query.peek().is_some() {
+        show_search.set(false);
+        set_search_query(None);
+      }
+      if *current_list_type.peek() != list_type {
+        current_list_type.set(list_type);
+      }
+    }
+    Some(Route::Search(query)) => {
+      show_search.set(true);
+      if search_query.peek().as_ref() != Some(&query) {
+        set_search_query(Some(query));
+      }
+    }
+    _ => {}
+  });
+
+  // Swaps in the list of the tab switched to, keeping the one left in the cache.
+  // The scroll position is restored by the list view itself.
   use_effect(move || {
-    current_list_type.read();
-    info!("List type changed, resetting state.");
-    stories_signal.set(vec![]);
-    loaded_count.set(BATCH_SIZE);
-    error_signal.set(None);
-    scroll_controller.scroll_to(ScrollPosition::Start, ScrollDirection::Vertical);
+    let list_type = *current_list_type.read();
+    let is_searching = search_query.read().is_some();
+    let mut cache = list_cache.write();
+    if let Some(left) = *shown_list.peek() {
+      cache.save_progress(
+        left,
+        stories_signal.peek().clone(),
+        failed_stories.peek().clone(),
+        *loaded_count.peek(),
+        *focused_story.peek(),
+      );
+    }
+    match cache.get(list_type).filter(|list| !is_searching && list.loaded_count > 0) {
+      Some(list) => {
+        info!("Showing the cached {list_type} list.");
+        stories_signal.set(list.stories.clone());
+        failed_stories.set(list.failed.clone());
+        loaded_count.set(list.loaded_count);
+        focused_story.set(list.focused);
+      }
+      None => {
+        info!("List type or search changed, resetting state.");
+        stories_signal.set(vec![]);
+        loaded_count.set(BATCH_SIZE);
+        failed_stories.set(vec![]);
+        focused_story.set(None);
+      }
+    }
+    shown_list.set((!is_searching).then_some(list_type));
   });
 
   let mut story_ids_resource = {
@@ -80,10 +192,16 @@ fn app() -> Element {
       let list_type = *current_list_type.read();
       let service = api_service.clone();
       async move {
+        let cached_ids = list_cache.peek().get(list_type).map(|list| list.ids.clone());
+        if let Some(ids) = cached_ids {
+          info!("Using cached story IDs for: {list_type}");
+          return Ok(ids);
+        }
         info!("Fetching story IDs for: {list_type}");
         let result = service.fetch_story_ids(list_type).await;
-        if result.is_ok() {
+        if let Ok(ids) = &result {
           info!("Successfully fetched story IDs for: {list_type}");
+          list_cache.write().put_ids(list_type, ids.clone());
         }
         result
       }
@@ -94,73 +212,455 @@ fn app() -> Element {
     let api_service = api_service.clone();
     use_resource(move || {
       let current_best_ids = story_ids_resource.value().read().as_ref().cloned();
+      // While the ids are refetched the previous ones are still there; wait for the new ones.
+      let ids_pending = *story_ids_resource.state().read() == UseResourceState::Pending;
       let loaded_count_val = *loaded_count.read();
-      let already_loaded = stories_signal.read().len();
+      // Failed stories count as loaded, so the next batch continues after them.
+      let already_loaded = stories_signal.read().len() + failed_stories.read().len();
+      // Search results fill the list themselves.
+      let is_searching = search_query.read().is_some();
       let api_service = api_service.clone();
       async move {
-        if let Some(Ok(ids)) = current_best_ids {
-          if already_loaded < loaded_count_val && already_loaded < ids.len() {
-            is_loading_more.set(true);
-            let ids_to_fetch =
-              ids.iter().skip(already_loaded).take(loaded_count_val - already_loaded).cloned().collect::<Vec<_>>();
-            info!("Fetching {} story details in parallel...", ids_to_fetch.len());
-            let stories_futures = ids_to_fetch.into_iter().map(|id| {
-              let api_service = api_service.clone();
-              async move { api_service.fetch_story_content(id).await }
-            });
-            let results = stream::iter(stories_futures).buffer_unordered(10).collect::<Vec<_>>().await;
-            let mut new_stories = Vec::new();
-            for result in results {
-              match result {
-                Ok(story) => new_stories.push(story),
-                Err(e) => {
-                  let err_msg = format!("Failed to fetch/parse story: {}", e);
-                  error!("{}", err_msg);
-                  error_signal.set(Some(err_msg));
-                }
-              }
-            }
-            if !new_stories.is_empty() {
-              stories_signal.write().extend(new_stories);
-            }
-            is_loading_more.set(false);
+        if !is_searching
+          && !ids_pending
+          && let Some(Ok(ids)) = current_best_ids
+          && already_loaded < loaded_count_val
+          && already_loaded < ids.len()
+        {
+          is_loading_more.set(true);
+          let ids_to_fetch =
+            ids.iter().skip(already_loaded).take(loaded_count_val - already_loaded).cloned().collect::<Vec<_>>();
+          info!("Fetching {} story details in parallel...", ids_to_fetch.len());
+          let (new_stories, new_failures) = api_service.fetch_stories(&ids_to_fetch).await;
+          if !new_stories.is_empty() {
+            stories_signal.write().extend(new_stories);
           }
+          if !new_failures.is_empty() {
+            failed_stories.write().extend(new_failures);
+          }
+          is_loading_more.set(false);
         }
       }
     })
   };
 
+  // Loads one page of search results per `search_page`, appending them to the list.
+  let mut search_resource = use_resource(move || {
+    let query = search_query.read().clone();
+    let page = *search_page.read();
+    let search_service = search_service.clone();
+    async move {
+      let Some(query) = query else { return Ok(()) };
+      is_loading_more.set(true);
+      info!("Searching for {:?} (page {})", query.text, page);
+      let result = search_service.search(&query, page, BATCH_SIZE).await;
+      is_loading_more.set(false);
+      let results = result?;
+      // Comment hits resolve to their story, so the same story can show up repeatedly.
+      let mut stories = stories_signal.write();
+      for story in results.stories {
+        if !stories.iter().any(|s| s.id == story.id) {
+          stories.push(story);
+        }
+      }
+      search_has_more.set(results.has_more);
+      Ok(())
+    }
+  });
+
+  // While offline, periodically check whether the API is reachable again and
+  // refresh the current list once it is.
+  use_future({
+    let api_service = api_service.clone();
+    move || {
+      let api_service = api_service.clone();
+      async move {
+        loop {
+          tokio::time::sleep(OFFLINE_PROBE_INTERVAL).await;
+          if api_service.is_offline() && api_service.probe().await {
+            info!("Connection restored, refreshing story list.");
+            list_cache.write().invalidate(*current_list_type.peek());
+            story_ids_resource.restart();
+          }
+        }
+      }
+    }
+  });
+
+  // Whether the current list has stories or search results left to load.
+  let has_more = use_memo(move || {
+    if search_query.read().is_some() {
+      *search_has_more.read()
+    } else {
+      matches!(story_ids_resource.value().read().as_ref(), Some(Ok(ids)) if *loaded_count.read() < ids.len())
+    }
+  });
+
+  // Requests the next batch of stories or search results.
+  let load_more = use_callback(move |()| {
+    if !*has_more.peek() || *is_loading_more.peek() {
+      return;
+    }
+    if search_query.peek().is_some() {
+      info!("Loading search page {}.", *search_page.peek() + 1);
+      search_has_more.set(false);
+      search_page += 1;
+    } else if let Some(Ok(ids)) = story_ids_resource.value().peek().as_ref() {
+      let next = (*loaded_count.peek() + BATCH_SIZE).min(ids.len());
+      info!("Loading up to {} stories.", next);
+      loaded_count.set(next);
+    }
+  });
+
+  let mut opened_from = use_context_provider(|| OpenedFrom { ids: Signal::new(Vec::new()) });
+
+  // Shared so that the detail view can walk through the loaded stories.
+  use_context_provider(|| LoadedStories {
+    stories: stories_signal,
+    order: story_order,
+    focused: focused_story,
+    has_more,
+    is_loading_more,
+    load_more,
+  });
+
   use_effect(move || {
     let y = scroll_controller.y();
     let layout = scroll_controller.layout();
     let y_val = *y.read();
     let layout_val = layout.read();
     let end = layout_val.inner.height - layout_val.area.height();
-    if !*is_loading_more.read()
+    let near_end = !*is_loading_more.read()
       && layout_val.inner.height > layout_val.area.height()
-      && -y_val > end as i32 - SCROLL_END_MARGIN
-    {
-      if let Some(Ok(ids)) = story_ids_resource.value().read().as_ref() {
-        let current = *loaded_count.read();
-        let next = (current + BATCH_SIZE).min(ids.len());
-        if next > current {
-          info!("Infinite scroll triggered: loading up to {} stories.", next);
-          loaded_count.set(next);
+      && -y_val > end as i32 - SCROLL_END_MARGIN;
+    if near_end && *has_more.read() {
+      info!("Infinite scroll triggered.");
+      load_more.call(());
+    }
+  });
+
+  let mut refresh = move || {
+    if search_query.read().is_some() {
+      info!("Refreshing search results...");
+      stories_signal.set(vec![]);
+      focused_story.set(None);
+      search_has_more.set(false);
+      // Going back to the first page refetches it by itself; already on it, the
+      // resource is restarted.
+      if *search_page.peek() == 0 {
+        search_resource.restart();
+      } else {
+        search_page.set(0);
+      }
+    } else {
+      info!("Refreshing story list...");
+      list_cache.write().invalidate(*current_list_type.read());
+      stories_signal.set(vec![]);
+      failed_stories.set(vec![]);
+      loaded_count.set(BATCH_SIZE);
+      focused_story.set(None);
+      story_ids_resource.restart();
+    }
+    scroll_controller.scroll_to(ScrollPosition::Start, ScrollDirection::Vertical);
+  };
+
+  let mut select_list_type = move |list_type: StoryListType| router.push(Route::List(list_type));
+
+  let mut toggle_theme = move || {
+    let new_mode = if *theme_mode.read() == ThemeMode::Light { ThemeMode::Dark } else { ThemeMode::Light };
+    theme_mode.set(new_mode);
+  };
+
+  let mut toggle_hide_read = move || {
+    let hide_read = !read_stories.peek().hide_read();
+    read_stories.write().set_hide_read(hide_read);
+  };
+
+  let mut toggle_only_highlighted = move || {
+    let only_highlighted = !highlights.peek().only_highlighted();
+    highlights.write().set_only_highlighted(only_highlighted);
+  };
+
+  // Moves to the tab `step` places to the right, wrapping around.
+  let mut cycle_tab = move |step: usize| {
+    let count = StoryListType::ALL.len();
+    let current = StoryListType::ALL.iter().position(|list_type| *list_type == *current_list_type.read()).unwrap_or(0);
+    select_list_type(StoryListType::ALL[(current + step) % count]);
+  };
+
+  // Shows the search bar, returning to the list (or search results) first if needed.
+  let mut open_search = move || {
+    if !router.route().is_list() {
+      let route = match search_query.read().clone() {
+        Some(query) => Route::Search(query),
+        None => Route::List(*current_list_type.read()),
+      };
+      router.push(route);
+    }
+    show_search.set(true);
+  };
+
+  let mut close_search = move || {
+    show_search.set(false);
+    if matches!(router.route(), Route::Search(_)) {
+      router.push(Route::List(*current_list_type.read()));
+    }
+  };
+
+  let run_command = move |command: Command| match command {
+    Command::SelectList(list_type) => select_list_type(list_type),
+    Command::NextTab => cycle_tab(1),
+    Command::PreviousTab => cycle_tab(StoryListType::ALL.len() - 1),
+    Command::ToggleTheme => toggle_theme(),
+    Command::ToggleHideRead => toggle_hide_read(),
+    Command::ToggleOnlyHighlighted => toggle_only_highlighted(),
+    Command::Refresh => refresh(),
+    Command::Back => router.back(),
+    Command::Forward => router.forward(),
+    Command::CloseTab => {
+      if let Some(key) = router.shown_tab() {
+        router.close_tab(key);
+      }
+    }
+    Command::ShowSaved => router.push(Route::Saved),
+    Command::ShowHistory => router.push(Route::History),
+    Command::ManageMutes => router.push(Route::Mutes),
+    Command::ManageHighlights => router.push(Route::Highlights),
+    Command::Search(Some(text)) => router.push(Route::Search(SearchQuery { text, ..SearchQuery::default() })),
+    Command::Search(None) => open_search(),
+    Command::OpenUser(name) => router.push(Route::User(name)),
+    Command::OpenStory(story) => {
+      let id = story.id;
+      opened_from.ids.set(Vec::new());
+      selected_story_data.set(Some(*story));
+      router.push(Route::Item(id));
+    }
+  };
+
+  // Shortcuts that work across views; the views handle their own navigation keys.
+  let key_actions = use_key_actions();
+  let onglobalkeydown = move |e: KeyboardEvent| {
+    // The tabs are shown next to stories and profiles on wide windows.
+    let on_list = router.route().is_list() || *is_wide.peek();
+    match key_actions.action(&e, Scope::Global) {
+      Some(KeyAction::SelectTab(index)) if on_list => {
+        if let Some(&list_type) = StoryListType::ALL.get(index) {
+          select_list_type(list_type);
+        }
+      }
+      Some(KeyAction::NextTab) if on_list => cycle_tab(1),
+      Some(KeyAction::PreviousTab) if on_list => cycle_tab(StoryListType::ALL.len() - 1),
+      Some(KeyAction::Refresh) => refresh(),
+      Some(KeyAction::ToggleTheme) => toggle_theme(),
+      Some(KeyAction::CommandPalette) => show_palette.set(true),
+      Some(KeyAction::Back) => router.back(),
+      Some(KeyAction::Forward) => router.forward(),
+      Some(KeyAction::CloseTab) => {
+        if let Some(key) = router.shown_tab() {
+          router.close_tab(key);
         }
       }
+      _ => {}
     }
-  });
+  };
+
+  // The back and forward buttons some mice have.
+  let onglobalmousedown = move |e: MouseEvent| match e.trigger_button {
+    Some(MouseButton::Back) => router.back(),
+    Some(MouseButton::Forward) => router.forward(),
+    _ => {}
+  };
 
   // --- Render ---
   let theme = theme_signal.read();
+  let route = router.route();
+  let entry_id = router.entry_id();
+  let list_entry_id = router.list_entry_id().unwrap_or_default();
+  let list_route = router.list_route();
+  let showing_stories = matches!(list_route, Some(Route::List(_) | Route::Search(_)));
+  // How many of the loaded stories the mutes leave out.
+  let muted_count = {
+    let mutes = mutes.read();
+    stories_signal.read().iter().filter(|story| mutes.hides_story(story)).count()
+  };
+
+  // The tabs and the list of the list or search the current page belongs to.
+  let list_pane = rsx! {
+      rect {
+          width: "100%",
+          height: "100%",
+          direction: "vertical",
+
+          if *show_search.read() {
+              SearchBar {
+                  initial: search_query.read().clone(),
+                  on_search: move |query| router.push(Route::Search(query)),
+                  on_close: move |_| close_search(),
+              }
+          }
+
+          // Tabs for selecting the story list type
+          rect {
+              width: "100%",
+              height: "auto",
+              direction: "horizontal",
+              padding: "6",
+              background: "{theme.color.background_card}",
+              main_align: "space-around",
+              border: "1 solid {theme.color.border}",
+              corner_radius: "6",
+
+              for list_type in StoryListType::ALL {
+                  StoryTab {
+                      title: list_type.to_string(),
+                      is_active: showing_stories && search_query.read().is_none() && *current_list_type.read() == list_type,
+                      onclick: move |_| select_list_type(list_type),
+                  }
+              }
+              StoryTab {
+                  title: "Saved".to_string(),
+                  is_active: list_route == Some(Route::Saved),
+                  onclick: move |_| router.push(Route::Saved),
+              }
+              StoryTab {
+                  title: "History".to_string(),
+                  is_active: list_route == Some(Route::History),
+                  onclick: move |_| router.push(Route::History),
+              }
+          }
+
+          if list_route == Some(Route::Saved) {
+              SavedView { key: "{list_entry_id}", selected_story_data }
+          } else if list_route == Some(Route::History) {
+              HistoryView { key: "{list_entry_id}" }
+          } else {
+              rect {
+                  width: "100%",
+                  direction: "horizontal",
+                  content: "flex",
+                  cross_align: "center",
+                  CursorArea {
+                      icon: CursorIcon::Pointer,
+                      label {
+                          onclick: move |_| router.push(Route::Mutes),
+                          margin: "0 8",
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_s}",
+                          color: "{theme.color.text_alt}",
+                          "{muted_count} muted · Manage"
+                      }
+                  }
+                  CursorArea {
+                      icon: CursorIcon::Pointer,
+                      label {
+                          onclick: move |_| router.push(Route::Highlights),
+                          margin: "0 8",
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_s}",
+                          color: "{theme.color.text_alt}",
+                          "Highlights"
+                      }
+                  }
+                  rect { width: "flex(1)" }
+                  Tile {
+                      onselect: move |_| toggle_only_highlighted(),
+                      leading: rsx! { Checkbox { selected: highlights.read().only_highlighted() } },
+                      label {
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_s}",
+                          color: "{theme.color.text_alt}",
+                          "Only highlighted"
+                      }
+                  }
+                  Tile {
+                      onselect: move |_| toggle_hide_read(),
+                      leading: rsx! { Checkbox { selected: read_stories.read().hide_read() } },
+                      label {
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_s}",
+                          color: "{theme.color.text_alt}",
+                          "Hide read"
+                      }
+                  }
+              }
+
+              ListFilterBar { filter: list_filter }
+
+              // The actual list of stories, recreated per visit to restore its scroll position
+              StoryListView {
+                  key: "{list_entry_id}",
+                  stories_signal,
+                  story_order,
+                  list_filter,
+                  failed_stories,
+                  best_story_ids_resource: story_ids_resource,
+                  search_resource,
+                  is_searching: search_query.read().is_some(),
+                  loaded_count,
+                  is_loading_more,
+                  selected_story_data,
+                  focused_story,
+                  scroll_controller,
+              }
+          }
+      }
+  };
+
+  // The stories open in tabs and the profile opened from the list, shown in place
+  // of the list on narrow windows and next to it on wide ones.
+  let page = rsx! {
+      rect {
+          width: "100%",
+          height: "100%",
+          direction: "vertical",
+          ItemTabs { selected_story: selected_story_data }
+          match route.clone() {
+              Route::User(name) => rsx! {
+                  UserView { key: "{entry_id}", username: name, selected_story_data }
+              },
+              Route::Highlights => rsx! {
+                  HighlightsView { key: "{entry_id}" }
+              },
+              Route::Mutes => rsx! {
+                  MutesView { key: "{entry_id}" }
+              },
+              Route::Item(_) => rsx! {},
+              Route::List(_) | Route::Search(_) | Route::Saved | Route::History => rsx! {
+                  rect {
+                      width: "100%",
+                      height: "fill",
+                      main_align: "center",
+                      cross_align: "center",
+                      label {
+                          font_family: "{theme.font.sans}",
+                          font_size: "{theme.size.text_l}",
+                          color: "{theme.color.text_alt}",
+                          "Select a story to read it here."
+                      }
+                  }
+              },
+          }
+      }
+  };
+  let shown_panes = if *is_wide.read() {
+    ShownPanes::Both
+  } else if route.is_list() {
+    ShownPanes::Left
+  } else {
+    ShownPanes::Right
+  };
+
   rsx! {
       rect {
+          reference: root_reference,
           width: "100%",
           height: "100%",
           direction: "vertical",
           background: "{theme.color.background_page}",
           color: "{theme.color.base}",
           padding: "10",
+          onglobalkeydown,
+          onglobalmousedown,
 
           // Header
           rect {
@@ -192,15 +692,21 @@ fn app() -> Element {
               rect {
                   direction: "horizontal",
                   cross_align: "center",
+                  if *is_offline.read() {
+                      OfflineBadge {}
+                      Spacer { width: "8" }
+                  }
                   IconButton {
-                      onclick: move |_| {
-                          let new_mode = if *theme_mode.read() == ThemeMode::Light {
-                              ThemeMode::Dark
-                          } else {
-                              ThemeMode::Light
-                          };
-                          theme_mode.set(new_mode);
-                      },
+                      onclick: move |_| router.back(),
+                      icon: rsx! { IconBack { enabled: router.can_go_back() } }
+                  }
+                  IconButton {
+                      onclick: move |_| router.forward(),
+                      icon: rsx! { IconForward { enabled: router.can_go_forward() } }
+                  }
+                  Spacer { width: "8" }
+                  IconButton {
+                      onclick: move |_| toggle_theme(),
                       icon: rsx! {
                           if *theme_mode.read() == ThemeMode::Light {
                               IconMoon {}
@@ -210,70 +716,45 @@ fn app() -> Element {
                       }
                   }
                   Spacer { width: "8" }
+                  IconButton {
+                      onclick: move |_| {
+                          if *show_search.read() {
+                              close_search();
+                          } else {
+                              open_search();
+                          }
+                      },
+                      icon: rsx! { IconSearch {} }
+                  }
+                  Spacer { width: "8" }
                   if story_ids_resource.value().read().is_none() {
                       label { font_size: "{theme.size.text_xl}", "⏳" }
                   } else {
                       IconButton {
-                          onclick: move |_| {
-                              info!("Refreshing story list...");
-                              story_ids_resource.restart();
-                          },
+                          onclick: move |_| refresh(),
                           icon: rsx! { label { font_size: "{theme.size.text_xl}", color: "{theme.color.accent_text}", "🔄" } }
                       }
                   }
               }
           }
 
-          // Viewport
-          if *current_view.read() == CurrentView::List {
-              rect {
-                  width: "100%",
-                  height: "100%",
-                  direction: "vertical",
+          if *show_keymap_issues.read() {
+              KeymapIssuesBanner {
+                  issues: keymap.read().issues().to_vec(),
+                  on_dismiss: move |_| show_keymap_issues.set(false),
+              }
+          }
 
-                  // Tabs for selecting the story list type
-                  rect {
-                      width: "100%",
-                      height: "auto",
-                      direction: "horizontal",
-                      padding: "6",
-                      background: "{theme.color.background_card}",
-                      main_align: "space-around",
-                      border: "1 solid {theme.color.border}",
-                      corner_radius: "6",
-
-                      for list_type in [StoryListType::Best, StoryListType::Top, StoryListType::New, StoryListType::Ask, StoryListType::Show, StoryListType::Job] {
-                          StoryTab {
-                              title: list_type.to_string(),
-                              is_active: *current_list_type.read() == list_type,
-                              onclick: move |_| {
-                                  if *current_list_type.read() != list_type {
-                                      current_list_type.set(list_type);
-                                  }
-                              }
-                          }
-                      }
-                  }
+          // Viewport
+          // Both panes stay mounted on narrow windows, so that the list and the open
+          // stories keep their state while the other is shown.
+          SplitPane { left: list_pane, right: page, shown: shown_panes }
 
-                  // The actual list of stories
-                  StoryListView {
-                      stories_signal,
-                      error_signal,
-                      best_story_ids_resource: story_ids_resource,
-                      loaded_count,
-                      is_loading_more,
-                      current_view,
-                      selected_story_data,
-                      scroll_controller,
-                  }
-              }
-          } else {
-              // The detail view does not include the tabs.
-              StoryDetailView {
-                  story_data: selected_story_data,
-                  on_back: move |_| {
-                      current_view.set(CurrentView::List);
-                  }
+          if *show_palette.read() {
+              CommandPalette {
+                  recent: palette_recent,
+                  on_command: run_command,
+                  on_close: move |_| show_palette.set(false),
               }
           }
       }
@@ -284,4 +765,5 @@ fn app() -> Element {
 fn main() {
   env_logger::init();
   launch_with_props(app, "Hacker News", (900.0, 900.0));
+  store::flush();
 }
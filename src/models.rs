@@ -1,18 +1,67 @@
+use crate::utils::error::ApiError;
 use freya::prelude::Signal;
 use jiff::Timestamp;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+/// The kind of an item, as given by its `type` field.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+  #[default]
+  Story,
+  Comment,
+  Job,
+  Poll,
+  PollOpt,
+}
+
+// Stories, job postings and polls share this shape; `kind` tells them apart.
+// Serializable so that saved stories can be kept in the bookmarks.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct Story {
   pub id: u32,
+  #[serde(rename = "type", default)]
+  pub kind: ItemKind,
   pub title: Option<String>,
   pub url: Option<String>,
+  /// The body of self posts (Ask HN, Show HN, jobs and polls), as HTML.
+  pub text: Option<String>,
   pub by: Option<String>,
   pub score: Option<u32>,
   #[serde(default, with = "jiff::fmt::serde::timestamp::second::optional")]
   pub time: Option<Timestamp>,
   pub descendants: Option<u32>,
   pub kids: Option<Vec<u32>>,
+  /// The ids of a poll's options, in display order.
+  pub parts: Option<Vec<u32>>,
+  #[serde(default)]
+  pub dead: bool,
+  #[serde(default)]
+  pub deleted: bool,
+}
+
+impl Story {
+  /// The host the story links to, if it is not a self post.
+  pub fn domain(&self) -> Option<&str> {
+    self.url.as_deref().and_then(domain)
+  }
+}
+
+/// The host a link points to, without a leading `www.`, e.g. `example.com`.
+pub fn domain(url: &str) -> Option<&str> {
+  let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+  let host = rest.split(['/', '?', '#']).next()?.rsplit('@').next()?;
+  let host = host.split(':').next()?;
+  let host = host.strip_prefix("www.").unwrap_or(host);
+  (!host.is_empty()).then_some(host)
+}
+
+// A story whose details could not be loaded, shown as a placeholder in the list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedStory {
+  pub id: u32,
+  pub error: ApiError,
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -20,7 +69,7 @@ pub enum FetchState {
   #[default]
   Idle,
   Loading,
-  Failed,
+  Failed(ApiError),
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -32,8 +81,12 @@ pub struct Comment {
   #[serde(default, with = "jiff::fmt::serde::timestamp::second::optional")]
   pub time: Option<Timestamp>,
   pub kids: Option<Vec<u32>>,
+  /// The comment or story this comment replies to.
+  pub parent: Option<u32>,
   #[serde(default)]
   pub deleted: bool,
+  #[serde(default)]
+  pub dead: bool,
 
   // --- UI State Fields ---
   #[serde(skip)]
@@ -44,3 +97,189 @@ pub struct Comment {
   #[serde(skip)]
   pub fetch_state: Signal<FetchState>,
 }
+
+/// A single option of a poll.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PollOption {
+  pub id: u32,
+  /// The poll this option belongs to.
+  pub poll: Option<u32>,
+  pub by: Option<String>,
+  pub text: Option<String>,
+  /// The number of votes for this option.
+  #[serde(default)]
+  pub score: u32,
+}
+
+// Any item returned by the API, told apart by its `type` field. Jobs and polls are
+// decoded into a `Story` as well, since story lists mix all three; decoding is done
+// by hand rather than as an internally tagged enum so `Story::kind` keeps the tag.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "Value")]
+pub enum Item {
+  Story(Story),
+  Comment(Comment),
+  Job(Story),
+  Poll(Story),
+  PollOpt(PollOption),
+  /// An item of an unknown type, or without one.
+  Other,
+}
+
+impl Item {
+  /// The item as shown in story lists and the detail view: a story, job or poll.
+  pub fn into_story(self) -> Option<Story> {
+    match self {
+      Item::Story(story) | Item::Job(story) | Item::Poll(story) => Some(story),
+      Item::Comment(_) | Item::PollOpt(_) | Item::Other => None,
+    }
+  }
+}
+
+impl TryFrom<Value> for Item {
+  type Error = serde_json::Error;
+
+  fn try_from(value: Value) -> Result<Self, Self::Error> {
+    let kind = value.get("type").and_then(|kind| ItemKind::deserialize(kind).ok());
+    Ok(match kind {
+      Some(ItemKind::Story) => Item::Story(serde_json::from_value(value)?),
+      Some(ItemKind::Job) => Item::Job(serde_json::from_value(value)?),
+      Some(ItemKind::Poll) => Item::Poll(serde_json::from_value(value)?),
+      Some(ItemKind::Comment) => Item::Comment(serde_json::from_value(value)?),
+      Some(ItemKind::PollOpt) => Item::PollOpt(serde_json::from_value(value)?),
+      None => Item::Other,
+    })
+  }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct User {
+  pub id: String,
+  #[serde(default, with = "jiff::fmt::serde::timestamp::second::optional")]
+  pub created: Option<Timestamp>,
+  #[serde(default)]
+  pub karma: i64,
+  /// The profile's self-description, as HTML.
+  pub about: Option<String>,
+  /// Ids of the user's stories, comments, polls and jobs, newest first.
+  #[serde(default)]
+  pub submitted: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dioxus::dioxus_core::{ScopeId, VNode, VirtualDom};
+  use serde_json::json;
+
+  // Comments hold signals, which need a scope to be created in.
+  fn decode(value: Value) -> Item {
+    let dom = VirtualDom::new(VNode::empty);
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| serde_json::from_value(value).unwrap()))
+  }
+
+  #[test]
+  fn stories_decode_with_their_kind() {
+    let Item::Story(story) = decode(json!({
+      "id": 8863, "type": "story", "by": "dhouston", "time": 1175714200, "title": "My YC app: Dropbox",
+      "url": "http://www.getdropbox.com/u/2/screencast.html", "score": 111, "descendants": 71, "kids": [9224, 8917]
+    })) else {
+      panic!("not a story");
+    };
+    assert_eq!(story.kind, ItemKind::Story);
+    assert_eq!(story.title.as_deref(), Some("My YC app: Dropbox"));
+    assert_eq!(story.time, Some(Timestamp::from_second(1175714200).unwrap()));
+    assert_eq!(story.kids, Some(vec![9224, 8917]));
+    assert!(!story.dead && !story.deleted);
+  }
+
+  #[test]
+  fn comments_decode_with_their_parent() {
+    let Item::Comment(comment) = decode(json!({
+      "id": 2921983, "type": "comment", "by": "norvig", "parent": 2921506, "text": "Aw shucks", "time": 1314211127
+    })) else {
+      panic!("not a comment");
+    };
+    assert_eq!(comment.parent, Some(2921506));
+    assert_eq!(comment.text.as_deref(), Some("Aw shucks"));
+    assert_eq!(comment.kids, None);
+  }
+
+  #[test]
+  fn jobs_and_polls_have_their_own_variants() {
+    let Item::Job(job) =
+      decode(json!({ "id": 192327, "type": "job", "title": "Justin.tv is hiring", "text": "<p>Hi</p>" }))
+    else {
+      panic!("not a job");
+    };
+    assert_eq!(job.kind, ItemKind::Job);
+    assert_eq!(job.text.as_deref(), Some("<p>Hi</p>"));
+
+    let poll = decode(json!({ "id": 126809, "type": "poll", "title": "Poll", "parts": [126810, 126811], "score": 46 }));
+    let Item::Poll(ref story) = poll else { panic!("not a poll") };
+    assert_eq!(story.kind, ItemKind::Poll);
+    assert_eq!(story.parts, Some(vec![126810, 126811]));
+    assert_eq!(poll.into_story().map(|story| story.id), Some(126809));
+  }
+
+  #[test]
+  fn poll_options_decode_with_their_votes() {
+    let Item::PollOpt(option) =
+      decode(json!({ "id": 160705, "type": "pollopt", "poll": 160704, "text": "Yes", "score": 335 }))
+    else {
+      panic!("not a poll option");
+    };
+    assert_eq!((option.poll, option.text.as_deref(), option.score), (Some(160704), Some("Yes"), 335));
+    assert_eq!(Item::PollOpt(option).into_story(), None);
+  }
+
+  #[test]
+  fn deleted_and_dead_items_keep_their_flags() {
+    let Item::Comment(comment) = decode(json!({ "id": 1, "type": "comment", "deleted": true, "parent": 2, "time": 1 }))
+    else {
+      panic!("not a comment");
+    };
+    assert!(comment.deleted);
+    assert_eq!((comment.by, comment.text), (None, None));
+
+    let Item::Story(story) = decode(json!({ "id": 3, "type": "story", "dead": true, "title": "[flagged]" })) else {
+      panic!("not a story");
+    };
+    assert!(story.dead);
+  }
+
+  #[test]
+  fn null_and_unknown_items_are_other() {
+    assert_eq!(decode(Value::Null), Item::Other);
+    assert_eq!(decode(json!({ "id": 1, "type": "ad" })), Item::Other);
+    assert_eq!(decode(json!({ "id": 1 })), Item::Other);
+  }
+
+  #[test]
+  fn malformed_items_fail_to_decode() {
+    assert!(serde_json::from_value::<Item>(json!({ "id": "one", "type": "story" })).is_err());
+  }
+
+  #[test]
+  fn domains_drop_the_scheme_www_and_port() {
+    assert_eq!(domain("https://www.example.com/a/b?c#d"), Some("example.com"));
+    assert_eq!(domain("http://blog.example.com:8080/"), Some("blog.example.com"));
+    assert_eq!(domain("https://user:pw@example.com/"), Some("example.com"));
+    assert_eq!(domain("example.com/path"), Some("example.com"));
+    // Only a leading `www.` is dropped.
+    assert_eq!(domain("https://www2.example.com"), Some("www2.example.com"));
+    assert_eq!(domain("https://sub.www.example.com"), Some("sub.www.example.com"));
+  }
+
+  #[test]
+  fn missing_hosts_have_no_domain() {
+    assert_eq!(domain(""), None);
+    assert_eq!(domain("https://"), None);
+    assert_eq!(domain("https:///path"), None);
+    assert_eq!(Story { url: None, ..Story::default() }.domain(), None);
+    assert_eq!(
+      Story { url: Some("https://www.rust-lang.org".to_string()), ..Story::default() }.domain(),
+      Some("rust-lang.org")
+    );
+  }
+}
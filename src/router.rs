@@ -0,0 +1,446 @@
+//! In-app navigation: the typed routes of the app and a browser-like history of
+//! them, with back and forward, the scroll position of every visited page and the
+//! stories kept open in tabs.
+
+use crate::utils::api::StoryListType;
+use crate::utils::search::SearchQuery;
+use freya::prelude::*;
+use log::info;
+use std::collections::HashMap;
+
+// The most entries the history keeps; the oldest are dropped beyond it.
+const MAX_HISTORY_ENTRIES: usize = 200;
+// The most tabs kept open; opening another closes the oldest one not shown.
+const MAX_TABS: usize = 20;
+
+/// A page of the app.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Route {
+  /// A story list tab.
+  List(StoryListType),
+  /// The results of a search.
+  Search(SearchQuery),
+  /// A story, job or poll with its comments, by item id.
+  Item(u32),
+  /// A user's profile, by username.
+  User(String),
+  /// The stories and comments saved for later.
+  Saved,
+  /// The stories and profiles opened before.
+  History,
+  /// The hidden stories and the muted domains, users and titles.
+  Mutes,
+  /// The rules highlighting topics in titles and comments.
+  Highlights,
+}
+
+impl Route {
+  /// Whether the route shows a list of stories, i.e. a list tab, search results,
+  /// the saved items or the reading history.
+  pub fn is_list(&self) -> bool {
+    matches!(self, Route::List(_) | Route::Search(_) | Route::Saved | Route::History)
+  }
+}
+
+// A visit to a route. Ids are unique within the session, so that visiting the same
+// route twice keeps two scroll positions.
+#[derive(Clone)]
+struct Entry {
+  id: usize,
+  route: Route,
+}
+
+struct History {
+  entries: Vec<Entry>,
+  index: usize,
+  next_id: usize,
+}
+
+impl History {
+  // The entry of the list or search the current page was reached from, which is
+  // the current entry itself on list pages.
+  fn list_entry(&self) -> Option<&Entry> {
+    self.entries[..=self.index].iter().rev().find(|entry| entry.route.is_list())
+  }
+
+  // Drops the oldest entries beyond `MAX_HISTORY_ENTRIES`.
+  fn trim(&mut self) {
+    let excess = self.entries.len().saturating_sub(MAX_HISTORY_ENTRIES).min(self.index);
+    self.entries.drain(..excess);
+    self.index -= excess;
+  }
+}
+
+/// A story kept open in a tab. Navigating to a story shows its tab, or shows it in
+/// the current tab if it has none.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ItemTab {
+  /// Unique within the session, so that a tab keeps its identity when it shows
+  /// another story.
+  pub key: usize,
+  pub id: u32,
+}
+
+struct Tabs {
+  open: Vec<ItemTab>,
+  // The tab last shown, which navigating to a story not open in any tab reuses.
+  current: Option<usize>,
+  next_key: usize,
+}
+
+impl Tabs {
+  fn add(&mut self, id: u32) -> usize {
+    if self.open.len() >= MAX_TABS
+      && let Some(index) = self.open.iter().position(|tab| Some(tab.key) != self.current)
+    {
+      self.open.remove(index);
+    }
+    let key = self.next_key;
+    self.next_key += 1;
+    self.open.push(ItemTab { key, id });
+    key
+  }
+}
+
+/// The navigation history, shared through context by the app root.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Router {
+  history: Signal<History>,
+  tabs: Signal<Tabs>,
+  // Scroll offsets of the entries navigated away from; only ever peeked at.
+  scroll_offsets: Signal<HashMap<usize, i32>>,
+  // The scroll views currently shown, by the entry they belong to. Both the list
+  // and the page opened from it are shown side by side on wide windows.
+  mounted_scrolls: Signal<HashMap<usize, ScrollController>>,
+}
+
+impl Router {
+  /// The route currently shown.
+  pub fn route(&self) -> Route {
+    let history = self.history.read();
+    history.entries[history.index].route.clone()
+  }
+
+  /// Identifies the current visit, e.g. to key a view so that it is recreated for
+  /// each visit.
+  pub fn entry_id(&self) -> usize {
+    let history = self.history.read();
+    history.entries[history.index].id
+  }
+
+  /// The list or search the current page was reached from; the current route
+  /// itself on list pages.
+  pub fn list_route(&self) -> Option<Route> {
+    self.history.read().list_entry().map(|entry| entry.route.clone())
+  }
+
+  /// Identifies the visit of `list_route`, to key the list view with.
+  pub fn list_entry_id(&self) -> Option<usize> {
+    self.history.read().list_entry().map(|entry| entry.id)
+  }
+
+  /// The stories open in tabs, in tab order.
+  pub fn tabs(&self) -> Vec<ItemTab> {
+    self.tabs.read().open.clone()
+  }
+
+  /// The key of the tab shown, if the current route is a story.
+  pub fn shown_tab(&self) -> Option<usize> {
+    let Route::Item(id) = self.route() else { return None };
+    self.tabs.read().open.iter().find(|tab| tab.id == id).map(|tab| tab.key)
+  }
+
+  /// Opens a story in a new tab without showing it, unless it is already open.
+  pub fn open_in_background(&mut self, id: u32) {
+    let mut tabs = self.tabs.write();
+    if !tabs.open.iter().any(|tab| tab.id == id) {
+      info!("Opening item {id} in a background tab");
+      tabs.add(id);
+    }
+  }
+
+  /// Closes a tab. Closing the tab shown shows the one next to it instead, or the
+  /// list once no tab is left.
+  pub fn close_tab(&mut self, key: usize) {
+    let shown = self.shown_tab() == Some(key);
+    let next = {
+      let mut tabs = self.tabs.write();
+      let Some(index) = tabs.open.iter().position(|tab| tab.key == key) else { return };
+      tabs.open.remove(index);
+      if tabs.current == Some(key) {
+        tabs.current = None;
+      }
+      tabs.open.get(index).or_else(|| tabs.open.last()).map(|tab| tab.id)
+    };
+    if !shown {
+      return;
+    }
+    match (next, self.list_route()) {
+      (Some(id), _) => self.replace(Route::Item(id)),
+      (None, Some(list_route)) => self.push(list_route),
+      (None, None) => {}
+    }
+  }
+
+  pub fn can_go_back(&self) -> bool {
+    self.history.read().index > 0
+  }
+
+  pub fn can_go_forward(&self) -> bool {
+    let history = self.history.read();
+    history.index + 1 < history.entries.len()
+  }
+
+  /// Navigates to `route`, dropping the entries that could be gone forward to.
+  /// Navigating to the route already shown does nothing.
+  pub fn push(&mut self, route: Route) {
+    if self.history.peek().entries[self.history.peek().index].route == route {
+      return;
+    }
+    info!("Navigating to {route:?}");
+    self.save_scroll();
+    let mut history = self.history.write();
+    let id = history.next_id;
+    history.next_id += 1;
+    let index = history.index + 1;
+    history.entries.truncate(index);
+    history.entries.push(Entry { id, route });
+    history.index = index;
+    history.trim();
+    drop(history);
+    self.forget_scroll();
+    self.sync_tabs();
+  }
+
+  /// Navigates to `route` in place of the current entry, e.g. to walk to the next
+  /// story without making going back walk through all the previous ones.
+  pub fn replace(&mut self, route: Route) {
+    info!("Navigating to {route:?} in place of the current page");
+    self.save_scroll();
+    let mut history = self.history.write();
+    let id = history.next_id;
+    history.next_id += 1;
+    let index = history.index;
+    history.entries[index] = Entry { id, route };
+    drop(history);
+    self.forget_scroll();
+    self.sync_tabs();
+  }
+
+  pub fn back(&mut self) {
+    if self.history.peek().index > 0 {
+      self.save_scroll();
+      self.history.write().index -= 1;
+      self.sync_tabs();
+    }
+  }
+
+  pub fn forward(&mut self) {
+    let can_go_forward = {
+      let history = self.history.peek();
+      history.index + 1 < history.entries.len()
+    };
+    if can_go_forward {
+      self.save_scroll();
+      self.history.write().index += 1;
+      self.sync_tabs();
+    }
+  }
+
+  // Makes a tab show the story navigated to: its own tab if it has one, otherwise
+  // the current tab, or a new one if there is none.
+  fn sync_tabs(&mut self) {
+    let id = {
+      let history = self.history.peek();
+      let Route::Item(id) = history.entries[history.index].route else { return };
+      id
+    };
+    let mut tabs = self.tabs.write();
+    let current = tabs.current;
+    if let Some(tab) = tabs.open.iter().find(|tab| tab.id == id) {
+      tabs.current = Some(tab.key);
+    } else if let Some(tab) = tabs.open.iter_mut().find(|tab| Some(tab.key) == current) {
+      tab.id = id;
+    } else {
+      tabs.current = Some(tabs.add(id));
+    }
+  }
+
+  // Remembers where the pages shown before navigating were scrolled to.
+  fn save_scroll(&mut self) {
+    let mounted_scrolls = self.mounted_scrolls.peek();
+    let mut scroll_offsets = self.scroll_offsets.write();
+    for (id, scroll_controller) in mounted_scrolls.iter() {
+      scroll_offsets.insert(*id, *scroll_controller.y().peek());
+    }
+  }
+
+  // Forgets the scroll offsets of entries no longer in the history.
+  fn forget_scroll(&mut self) {
+    let history = self.history.peek();
+    self.scroll_offsets.write().retain(|id, _| history.entries.iter().any(|entry| entry.id == *id));
+  }
+}
+
+/// Creates the router, starting at `initial`, and provides it to all children.
+pub fn use_router_provider(initial: impl FnOnce() -> Route) -> Router {
+  let router = use_hook(|| Router {
+    history: Signal::new(History { entries: vec![Entry { id: 0, route: initial() }], index: 0, next_id: 1 }),
+    tabs: Signal::new(Tabs { open: Vec::new(), current: None, next_key: 0 }),
+    scroll_offsets: Signal::new(HashMap::new()),
+    mounted_scrolls: Signal::new(HashMap::new()),
+  });
+  use_context_provider(|| router)
+}
+
+/// Requires the `Router` to be provided as context.
+pub fn use_router() -> Router {
+  use_context::<Router>()
+}
+
+/// Scrolls a page's scroll view back to where it was when its history entry was
+/// last shown, and lets the router remember the position when navigating away.
+/// The page must be recreated for every visit, i.e. keyed by `Router::entry_id`.
+pub fn use_scroll_restoration(scroll_controller: ScrollController) {
+  let router = use_router();
+  let id = use_hook(|| {
+    let history = router.history.peek();
+    history.entries[history.index].id
+  });
+  use_restore_entry_scroll(router, Some(id), scroll_controller);
+}
+
+/// Like `use_scroll_restoration`, for the list view, which is kept while the pages
+/// opened from it are shown next to it and so must be keyed by `Router::list_entry_id`.
+pub fn use_list_scroll_restoration(scroll_controller: ScrollController) {
+  let router = use_router();
+  let id = use_hook(|| router.history.peek().list_entry().map(|entry| entry.id));
+  use_restore_entry_scroll(router, id, scroll_controller);
+}
+
+fn use_restore_entry_scroll(mut router: Router, id: Option<usize>, scroll_controller: ScrollController) {
+  use_hook(move || {
+    let Some(id) = id else { return };
+    let offset = router.scroll_offsets.peek().get(&id).copied().unwrap_or(0);
+    // Offsets beyond content that is still loading are clamped while rendering
+    // and take effect once it has loaded.
+    scroll_controller.y().set(offset);
+    router.mounted_scrolls.write().insert(id, scroll_controller);
+  });
+  use_drop(move || {
+    if let Some(id) = id {
+      router.mounted_scrolls.write().remove(&id);
+    }
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dioxus::dioxus_core::{ScopeId, VNode, VirtualDom};
+
+  fn history(len: usize, index: usize) -> History {
+    let entries = (0..len).map(|id| Entry { id, route: Route::Item(id as u32) }).collect();
+    History { entries, index, next_id: len }
+  }
+
+  #[test]
+  fn the_history_drops_its_oldest_entries() {
+    let mut history = history(MAX_HISTORY_ENTRIES + 5, MAX_HISTORY_ENTRIES + 4);
+    history.trim();
+    assert_eq!(history.entries.len(), MAX_HISTORY_ENTRIES);
+    assert_eq!(history.entries[0].id, 5);
+    assert_eq!(history.entries[history.index].id, MAX_HISTORY_ENTRIES + 4);
+  }
+
+  #[test]
+  fn the_current_entry_is_never_dropped() {
+    let mut history = history(MAX_HISTORY_ENTRIES + 5, 2);
+    history.trim();
+    assert_eq!(history.entries[history.index].id, 2);
+    assert_eq!(history.index, 0);
+
+    let mut short = self::history(3, 1);
+    short.trim();
+    assert_eq!((short.entries.len(), short.index), (3, 1));
+  }
+
+  #[test]
+  fn pages_belong_to_the_last_list_visited_before_them() {
+    let routes = [Route::Item(1), Route::List(StoryListType::New), Route::Item(2), Route::User("pg".to_string())];
+    let entries = routes.into_iter().enumerate().map(|(id, route)| Entry { id, route }).collect();
+    let mut history = History { entries, index: 3, next_id: 4 };
+    assert_eq!(history.list_entry().map(|entry| entry.id), Some(1));
+    history.index = 1;
+    assert_eq!(history.list_entry().map(|entry| entry.id), Some(1));
+    history.index = 0;
+    assert!(history.list_entry().is_none());
+  }
+
+  #[test]
+  fn opening_too_many_tabs_closes_the_oldest_not_shown() {
+    let mut tabs = Tabs { open: Vec::new(), current: None, next_key: 0 };
+    tabs.current = Some(tabs.add(0));
+    for id in 1..=MAX_TABS as u32 {
+      tabs.add(id);
+    }
+    assert_eq!(tabs.open.len(), MAX_TABS);
+    assert_eq!(tabs.open[0].id, 0);
+    assert_eq!(tabs.open[1].id, 2);
+  }
+
+  // Runs `f` with a router starting at the top stories, inside a runtime for its signals.
+  fn with_router(f: impl FnOnce(Router)) {
+    let dom = VirtualDom::new(VNode::empty);
+    dom.in_runtime(|| {
+      ScopeId::ROOT.in_runtime(|| {
+        f(Router {
+          history: Signal::new(History {
+            entries: vec![Entry { id: 0, route: Route::List(StoryListType::Top) }],
+            index: 0,
+            next_id: 1,
+          }),
+          tabs: Signal::new(Tabs { open: Vec::new(), current: None, next_key: 0 }),
+          scroll_offsets: Signal::new(HashMap::new()),
+          mounted_scrolls: Signal::new(HashMap::new()),
+        })
+      })
+    });
+  }
+
+  fn tab_ids(router: &Router) -> Vec<u32> {
+    router.tabs().iter().map(|tab| tab.id).collect()
+  }
+
+  #[test]
+  fn stories_open_in_the_current_tab_unless_they_have_their_own() {
+    with_router(|mut router| {
+      router.push(Route::Item(1));
+      router.push(Route::Item(2));
+      assert_eq!(tab_ids(&router), [2]);
+
+      router.open_in_background(3);
+      assert_eq!(tab_ids(&router), [2, 3]);
+      assert_eq!(router.route(), Route::Item(2));
+
+      router.push(Route::Item(3));
+      assert_eq!(router.shown_tab(), Some(router.tabs()[1].key));
+      router.push(Route::Item(4));
+      assert_eq!(tab_ids(&router), [2, 4]);
+    });
+  }
+
+  #[test]
+  fn closing_the_shown_tab_shows_the_next_one_then_the_list() {
+    with_router(|mut router| {
+      router.push(Route::Item(1));
+      router.open_in_background(2);
+      router.close_tab(router.shown_tab().unwrap());
+      assert_eq!(router.route(), Route::Item(2));
+
+      router.close_tab(router.shown_tab().unwrap());
+      assert_eq!(router.route(), Route::List(StoryListType::Top));
+      assert!(router.tabs().is_empty());
+    });
+  }
+}
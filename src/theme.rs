@@ -1,5 +1,8 @@
 //! Centralized theme definition for the application.
 
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
 // An enum to represent the current theme mode.
 #[derive(Clone, Copy, PartialEq, Default)]
 pub enum ThemeMode {
@@ -52,6 +55,44 @@ pub struct ColorTheme {
   pub tab_background_hover: &'static str,
   pub tab_text_active: &'static str,
   pub tab_text_inactive: &'static str,
+  pub offline_background: &'static str,
+  pub focus_ring: &'static str,
+  pub highlight: HighlightTheme,
+}
+
+/// The colors highlight rules can mark text with, resolved by the theme.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display, Serialize, Deserialize)]
+pub enum HighlightColor {
+  #[default]
+  Orange,
+  Green,
+  Blue,
+  Purple,
+}
+
+impl HighlightColor {
+  pub const ALL: [HighlightColor; 4] =
+    [HighlightColor::Orange, HighlightColor::Green, HighlightColor::Blue, HighlightColor::Purple];
+}
+
+// Text colors for highlighted words, readable on the card background.
+#[derive(Clone, PartialEq)]
+pub struct HighlightTheme {
+  pub orange: &'static str,
+  pub green: &'static str,
+  pub blue: &'static str,
+  pub purple: &'static str,
+}
+
+impl HighlightTheme {
+  pub fn get(&self, color: HighlightColor) -> &'static str {
+    match color {
+      HighlightColor::Orange => self.orange,
+      HighlightColor::Green => self.green,
+      HighlightColor::Blue => self.blue,
+      HighlightColor::Purple => self.purple,
+    }
+  }
 }
 
 // The main Theme struct that combines all the sub-themes.
@@ -93,6 +134,14 @@ impl Theme {
         tab_background_hover: "rgb(235, 245, 255)",
         tab_text_active: "rgb(0, 50, 100)",
         tab_text_inactive: "rgb(50, 50, 50)",
+        offline_background: "rgb(90, 90, 90)",
+        focus_ring: "rgb(255, 102, 0)",
+        highlight: HighlightTheme {
+          orange: "rgb(210, 80, 0)",
+          green: "rgb(0, 135, 60)",
+          blue: "rgb(0, 95, 200)",
+          purple: "rgb(140, 40, 180)",
+        },
       },
     }
   }
@@ -125,6 +174,14 @@ impl Theme {
         tab_background_hover: "rgb(40, 40, 45)",
         tab_text_active: "rgb(20, 20, 20)",
         tab_text_inactive: "rgb(150, 150, 150)",
+        offline_background: "rgb(70, 70, 78)",
+        focus_ring: "rgb(255, 200, 80)",
+        highlight: HighlightTheme {
+          orange: "rgb(255, 150, 60)",
+          green: "rgb(90, 210, 120)",
+          blue: "rgb(110, 170, 255)",
+          purple: "rgb(200, 140, 255)",
+        },
       },
     }
   }
@@ -1,11 +1,22 @@
-//! Contains the centralized ApiService for all Hacker News network requests.
+//! Contains the centralized ApiService for all Hacker News data requests.
 
-use crate::models::{Comment, Story};
-use reqwest::Client;
+use crate::models::{Comment, FailedStory, Story, User};
+use crate::utils::backend::{self, HnBackend};
+use crate::utils::cache::ItemCache;
+use crate::utils::error::ApiError;
+use crate::utils::retry::RetryPolicy;
+use freya::prelude::*;
+use futures::{StreamExt, stream};
+use jiff::SignedDuration;
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::env;
+use std::sync::Arc;
 use strum_macros::Display;
 
 // --- Public Enum for Story List Types ---
-#[derive(Clone, Copy, PartialEq, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
 pub enum StoryListType {
   Best,
   Top,
@@ -15,9 +26,11 @@ pub enum StoryListType {
   Job,
 }
 
-// ... (The rest of the file remains exactly the same) ...
 impl StoryListType {
-  fn as_api_str(&self) -> &str {
+  /// The story list tabs, in display order; number keys select them by position.
+  pub const ALL: [StoryListType; 6] = [Self::Best, Self::Top, Self::New, Self::Ask, Self::Show, Self::Job];
+
+  pub fn as_api_str(&self) -> &'static str {
     match self {
       Self::Best => "beststories",
       Self::Top => "topstories",
@@ -28,50 +41,337 @@ impl StoryListType {
     }
   }
 }
-const HN_API_BASE_URL: &str = "https://hacker-news.firebaseio.com/v0/";
-pub fn hn_item_url(id: u32) -> String {
-  format!("{}item/{}.json", HN_API_BASE_URL, id)
-}
-pub fn get_story_list_url(list_type: StoryListType) -> String {
-  format!("{}{}.json", HN_API_BASE_URL, list_type.as_api_str())
-}
+// How long a cached item is served without asking the network for a newer copy.
+const ITEM_MAX_AGE: SignedDuration = SignedDuration::from_mins(10);
+// How many stories of a batch are fetched at once.
+const STORY_FETCH_CONCURRENCY: usize = 10;
+
 #[derive(Clone)]
 pub struct ApiService {
-  client: Client,
+  backend: Arc<dyn HnBackend>,
+  cache: ItemCache,
+  retry: RetryPolicy,
+  // Flipped on whenever the network fails and cached data is served instead,
+  // and back off as soon as a request succeeds again.
+  offline: SyncSignal<bool>,
 }
 impl ApiService {
-  pub fn new() -> Self {
-    Self { client: Client::new() }
+  pub fn new(backend: Arc<dyn HnBackend>, cache: ItemCache, offline: SyncSignal<bool>) -> Self {
+    Self { backend, cache, retry: RetryPolicy::default(), offline }
   }
-  pub async fn fetch_story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, String> {
-    self
-      .client
-      .get(get_story_list_url(list_type))
-      .send()
-      .await
-      .map_err(|e| e.to_string())?
-      .json::<Vec<u32>>()
-      .await
-      .map_err(|e| e.to_string())
-  }
-  pub async fn fetch_story_content(&self, id: u32) -> Result<Story, String> {
+  pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+    self.retry = retry;
     self
-      .client
-      .get(hn_item_url(id))
-      .send()
-      .await
-      .map_err(|e| e.to_string())?
-      .json::<Story>()
-      .await
-      .map_err(|e| e.to_string())
-  }
-  pub async fn fetch_comment_content(&self, id: u32) -> Result<Comment, String> {
-    let url = hn_item_url(id);
-    let mut comment: Comment =
-      self.client.get(url).send().await.map_err(|e| e.to_string())?.json().await.map_err(|e| e.to_string())?;
+  }
+  /// Creates the service for the backend selected through the environment (see `backend::from_env`).
+  pub fn from_env(offline: SyncSignal<bool>) -> Self {
+    let backend = backend::from_env();
+    // Only live data is worth persisting; fixtures and recordings are local already.
+    let cache = if backend.is_live() { ItemCache::new() } else { ItemCache::disabled() };
+    let mut retry = RetryPolicy::default();
+    if let Some(max_attempts) = env::var("HN_MAX_ATTEMPTS").ok().and_then(|v| v.parse().ok()) {
+      retry.max_attempts = max_attempts;
+    }
+    Self::new(backend, cache, offline).with_retry_policy(retry)
+  }
+  pub fn is_offline(&self) -> bool {
+    *self.offline.peek()
+  }
+  pub async fn fetch_story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, ApiError> {
+    match self.retry_policy().run(|| self.backend.story_ids(list_type)).await {
+      Ok(ids) => {
+        self.set_offline(false);
+        self.cache.put_list(list_type, &ids).await;
+        Ok(ids)
+      }
+      Err(e) => {
+        self.set_offline(e.is_connection_error());
+        match self.cache.get_list(list_type).await {
+          Some(cached) if e.is_retryable() => {
+            warn!("Serving cached {list_type} list from {}: {e}", cached.fetched_at);
+            Ok(cached.ids)
+          }
+          _ => Err(e),
+        }
+      }
+    }
+  }
+  /// Checks whether the API is reachable again, updating the offline state accordingly.
+  pub async fn probe(&self) -> bool {
+    let reachable = self.backend.max_item().await.is_ok();
+    self.set_offline(!reachable);
+    reachable
+  }
+  pub async fn fetch_story_content(&self, id: u32) -> Result<Story, ApiError> {
+    self.fetch_item(id).await
+  }
+
+  /// Fetches a batch of stories in parallel, keeping the order of `ids`. Stories that
+  /// fail to load are returned separately, to be shown as placeholders.
+  pub async fn fetch_stories(&self, ids: &[u32]) -> (Vec<Story>, Vec<FailedStory>) {
+    let results = stream::iter(ids.iter().map(|&id| async move { (id, self.fetch_story_content(id).await) }))
+      .buffered(STORY_FETCH_CONCURRENCY)
+      .collect::<Vec<_>>()
+      .await;
+    let mut stories = Vec::new();
+    let mut failures = Vec::new();
+    for (id, result) in results {
+      match result {
+        Ok(story) => stories.push(story),
+        Err(error) => {
+          warn!("Failed to fetch/parse story {}: {}", id, error);
+          failures.push(FailedStory { id, error });
+        }
+      }
+    }
+    (stories, failures)
+  }
+  pub async fn fetch_comment_content(&self, id: u32) -> Result<Comment, ApiError> {
+    let mut comment: Comment = self.fetch_item(id).await?;
     comment.children = freya::prelude::Signal::new(vec![]);
     comment.is_expanded = freya::prelude::Signal::new(false);
     comment.fetch_state = freya::prelude::Signal::new(crate::models::FetchState::Idle);
     Ok(comment)
   }
+
+  pub async fn fetch_user(&self, name: &str) -> Result<User, ApiError> {
+    let result = self.retry_policy().run(|| self.backend.user(name)).await;
+    self.set_offline(result.as_ref().is_err_and(|e| e.is_connection_error()));
+    let value = result?;
+    if value.is_null() {
+      return Err(ApiError::NotFound);
+    }
+    Ok(serde_json::from_value(value)?)
+  }
+
+  /// Fetches any item, decoded as `T` (e.g. `Story`, `Comment` or `Item`).
+  ///
+  /// Serves the item from the on-disk cache while it is fresh, otherwise fetches it and
+  /// refreshes the cache. A stale copy is still preferred over a request that failed
+  /// for a transient reason.
+  pub async fn fetch_item<T: DeserializeOwned>(&self, id: u32) -> Result<T, ApiError> {
+    let cached = self.cache.get(id).await;
+    let value = match cached {
+      Some(entry) if entry.is_fresh(ITEM_MAX_AGE) => entry.item,
+      stale => match self.fetch_item_from_backend(id).await {
+        Ok(value) => {
+          self.set_offline(false);
+          self.cache.put(id, &value).await;
+          value
+        }
+        Err(e) => {
+          self.set_offline(e.is_connection_error());
+          match stale {
+            Some(entry) if e.is_retryable() => {
+              warn!("Serving stale cached item {id}: {e}");
+              entry.item
+            }
+            _ => return Err(e),
+          }
+        }
+      },
+    };
+    Ok(serde_json::from_value(value)?)
+  }
+
+  async fn fetch_item_from_backend(&self, id: u32) -> Result<Value, ApiError> {
+    let value = self.retry_policy().run(|| self.backend.item(id)).await?;
+    // The API answers `null` for ids that do not exist; never cache those.
+    if value.is_null() {
+      return Err(ApiError::NotFound);
+    }
+    Ok(value)
+  }
+
+  // Retrying is pointless while the API is known to be unreachable; the offline
+  // probe takes care of noticing when it comes back.
+  fn retry_policy(&self) -> RetryPolicy {
+    if self.is_offline() { RetryPolicy::none() } else { self.retry }
+  }
+
+  fn set_offline(&self, offline: bool) {
+    if *self.offline.peek() != offline {
+      info!("Connection state changed: {}", if offline { "offline" } else { "online" });
+      let mut signal = self.offline;
+      signal.set(offline);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::backend::{FixtureBackend, ReplayBackend};
+  use crate::utils::cache::CachedItem;
+  use async_trait::async_trait;
+  use dioxus::dioxus_core::{ScopeId, VNode, VirtualDom};
+  use jiff::Timestamp;
+  use serde_json::json;
+
+  // A backend whose every request fails with the same error.
+  struct FailingBackend(ApiError);
+
+  #[async_trait]
+  impl HnBackend for FailingBackend {
+    async fn story_ids(&self, _: StoryListType) -> Result<Vec<u32>, ApiError> {
+      Err(self.0.clone())
+    }
+
+    async fn item(&self, _: u32) -> Result<Value, ApiError> {
+      Err(self.0.clone())
+    }
+
+    async fn user(&self, _: &str) -> Result<Value, ApiError> {
+      Err(self.0.clone())
+    }
+
+    async fn max_item(&self) -> Result<u32, ApiError> {
+      Err(self.0.clone())
+    }
+  }
+
+  // The service over `backend` and `cache`, without retries. The virtual DOM owns
+  // the offline signal and has to outlive the service.
+  fn service(backend: impl HnBackend + 'static, cache: ItemCache) -> (VirtualDom, ApiService) {
+    let dom = VirtualDom::new(VNode::empty);
+    let offline = dom.in_runtime(|| SyncSignal::new_maybe_sync_in_scope(false, ScopeId::ROOT));
+    let service = ApiService::new(Arc::new(backend), cache, offline).with_retry_policy(RetryPolicy::none());
+    (dom, service)
+  }
+
+  fn network_error() -> ApiError {
+    ApiError::Network("connection refused".to_string())
+  }
+
+  #[tokio::test]
+  async fn a_cached_list_is_served_while_offline() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ItemCache::in_dir(dir.path().to_path_buf());
+    cache.put_list(StoryListType::Best, &[1, 2]).await;
+    let (_dom, api) = service(FailingBackend(network_error()), cache);
+
+    assert_eq!(api.fetch_story_ids(StoryListType::Best).await, Ok(vec![1, 2]));
+    assert!(api.is_offline());
+  }
+
+  #[tokio::test]
+  async fn an_uncached_list_fails_with_a_connection_error_while_offline() {
+    let (_dom, api) = service(FailingBackend(network_error()), ItemCache::disabled());
+
+    let error = api.fetch_story_ids(StoryListType::Best).await.unwrap_err();
+    assert!(error.is_connection_error());
+    assert!(api.is_offline());
+  }
+
+  #[tokio::test]
+  async fn a_stale_item_is_served_while_offline() {
+    let dir = tempfile::tempdir().unwrap();
+    let stale = CachedItem { fetched_at: Timestamp::UNIX_EPOCH, item: json!({ "id": 1, "title": "Stale" }) };
+    let cache = ItemCache::in_dir(dir.path().to_path_buf());
+    std::fs::write(dir.path().join("items").join("1.json"), serde_json::to_vec(&stale).unwrap()).unwrap();
+    let (_dom, api) = service(FailingBackend(network_error()), cache);
+
+    let story: Story = api.fetch_item(1).await.unwrap();
+    assert_eq!(story.title.as_deref(), Some("Stale"));
+    assert!(api.is_offline());
+  }
+
+  #[tokio::test]
+  async fn errors_that_are_not_about_the_connection_keep_the_app_online() {
+    for error in [ApiError::Http(500), ApiError::Decode("bad".to_string()), ApiError::NotFound] {
+      let (_dom, api) = service(FailingBackend(error.clone()), ItemCache::disabled());
+      assert_eq!(api.fetch_story_ids(StoryListType::Best).await, Err(error.clone()));
+      assert_eq!(api.fetch_item::<Story>(1).await.unwrap_err(), error);
+      assert!(!api.is_offline(), "{error:?} switched to offline");
+    }
+  }
+
+  #[tokio::test]
+  async fn a_deleted_item_is_not_served_from_a_stale_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ItemCache::in_dir(dir.path().to_path_buf());
+    let stale = CachedItem { fetched_at: Timestamp::UNIX_EPOCH, item: json!({ "id": 1, "title": "Gone" }) };
+    std::fs::write(dir.path().join("items").join("1.json"), serde_json::to_vec(&stale).unwrap()).unwrap();
+    let (_dom, api) = service(FixtureBackend::new(), cache);
+
+    assert_eq!(api.fetch_item::<Story>(1).await.unwrap_err(), ApiError::NotFound);
+    assert!(!api.is_offline());
+  }
+
+  #[tokio::test]
+  async fn a_successful_request_goes_back_online() {
+    let (_dom, api) = service(FixtureBackend::new().with_list(StoryListType::Best, vec![3]), ItemCache::disabled());
+    api.set_offline(true);
+
+    assert_eq!(api.fetch_story_ids(StoryListType::Best).await, Ok(vec![3]));
+    assert!(!api.is_offline());
+  }
+
+  fn fixture() -> FixtureBackend {
+    FixtureBackend::new()
+      .with_list(StoryListType::Best, vec![3, 1, 2])
+      .with_item(json!({ "id": 1, "type": "story", "title": "One", "score": 10 }))
+      .with_item(json!({ "id": 3, "type": "story", "title": "Three", "score": 30 }))
+      .with_item(json!({ "id": 4, "type": "comment", "parent": 1, "text": "A comment" }))
+      .with_user(json!({ "id": "alice", "karma": 5, "created": 1 }))
+  }
+
+  #[tokio::test]
+  async fn the_story_list_loads_from_a_fixture() {
+    let (_dom, api) = service(fixture(), ItemCache::disabled());
+
+    let ids = api.fetch_story_ids(StoryListType::Best).await.unwrap();
+    assert_eq!(ids, [3, 1, 2]);
+    let (stories, failures) = api.fetch_stories(&ids).await;
+    let titles: Vec<_> = stories.iter().map(|story| story.title.as_deref().unwrap()).collect();
+    assert_eq!(titles, ["Three", "One"]);
+    assert_eq!(failures, [FailedStory { id: 2, error: ApiError::NotFound }]);
+  }
+
+  #[tokio::test]
+  async fn items_and_users_load_from_a_fixture() {
+    let (dom, api) = service(fixture(), ItemCache::disabled());
+
+    assert_eq!(api.fetch_story_content(1).await.unwrap().score, Some(10));
+    // Comments carry signals, which only a component scope can create.
+    let comment =
+      dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| futures::executor::block_on(api.fetch_comment_content(4))));
+    assert_eq!(comment.unwrap().text.as_deref(), Some("A comment"));
+    assert_eq!(api.fetch_user("alice").await.unwrap().karma, 5);
+  }
+
+  #[tokio::test]
+  async fn a_missing_item_or_user_is_not_found() {
+    let (_dom, api) = service(fixture(), ItemCache::disabled());
+
+    assert_eq!(api.fetch_story_content(99).await.unwrap_err(), ApiError::NotFound);
+    assert_eq!(api.fetch_user("nobody").await.unwrap_err(), ApiError::NotFound);
+    assert!(!api.is_offline());
+  }
+
+  #[tokio::test]
+  async fn the_story_list_loads_from_a_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    std::fs::write(
+      &path,
+      concat!(
+        "{\"list\":{\"list\":\"beststories\",\"ids\":[1,2]}}\n",
+        "{\"item\":{\"id\":1,\"item\":{\"id\":1,\"type\":\"story\",\"title\":\"Replayed\"}}}\n",
+        "{\"item\":{\"id\":2,\"item\":null}}\n",
+      ),
+    )
+    .unwrap();
+    let (_dom, api) = service(ReplayBackend::load(&path).unwrap(), ItemCache::disabled());
+
+    let ids = api.fetch_story_ids(StoryListType::Best).await.unwrap();
+    let (stories, failures) = api.fetch_stories(&ids).await;
+    assert_eq!(stories.len(), 1);
+    assert_eq!(stories[0].title.as_deref(), Some("Replayed"));
+    assert_eq!(failures, [FailedStory { id: 2, error: ApiError::NotFound }]);
+    // Items missing from the recording are reported, not mistaken for being offline.
+    assert!(matches!(api.fetch_story_content(3).await, Err(ApiError::Unavailable(_))));
+    assert!(matches!(api.fetch_story_ids(StoryListType::Top).await, Err(ApiError::Unavailable(_))));
+    assert!(!api.is_offline());
+  }
 }
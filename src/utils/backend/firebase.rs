@@ -0,0 +1,96 @@
+//! The official Hacker News API, hosted on Firebase.
+
+use super::HnBackend;
+use crate::utils::api::StoryListType;
+use crate::utils::error::ApiError;
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::time::Duration;
+
+const HN_API_BASE_URL: &str = "https://hacker-news.firebaseio.com/v0/";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub struct FirebaseBackend {
+  client: Client,
+  base_url: String,
+}
+
+impl FirebaseBackend {
+  pub fn new() -> Self {
+    Self::with_base_url(HN_API_BASE_URL)
+  }
+
+  /// Points the backend at another server exposing the same API, e.g. a local stand-in.
+  pub fn with_base_url(base_url: impl Into<String>) -> Self {
+    let mut base_url = base_url.into();
+    if !base_url.ends_with('/') {
+      base_url.push('/');
+    }
+    // Building a client only fails if the TLS backend cannot be initialized.
+    let client = Client::builder().timeout(REQUEST_TIMEOUT).build().unwrap_or_default();
+    Self { client, base_url }
+  }
+
+  // The URL of `path` under the base URL, with every segment percent-encoded, so that
+  // names cannot reach outside their path segment.
+  fn url(&self, path: &[&str]) -> Result<Url, ApiError> {
+    let mut url = Url::parse(&self.base_url).map_err(|e| ApiError::Network(format!("invalid API URL: {e}")))?;
+    url
+      .path_segments_mut()
+      .map_err(|_| ApiError::Network(format!("invalid API URL: {}", self.base_url)))?
+      .pop_if_empty()
+      .extend(path);
+    Ok(url)
+  }
+
+  async fn get_json<T: DeserializeOwned>(&self, path: &[&str]) -> Result<T, ApiError> {
+    let response = self.client.get(self.url(path)?).send().await?.error_for_status()?;
+    Ok(response.json::<T>().await?)
+  }
+}
+
+#[async_trait]
+impl HnBackend for FirebaseBackend {
+  async fn story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, ApiError> {
+    self.get_json(&[&format!("{}.json", list_type.as_api_str())]).await
+  }
+
+  async fn item(&self, id: u32) -> Result<Value, ApiError> {
+    self.get_json(&["item", &format!("{id}.json")]).await
+  }
+
+  async fn user(&self, name: &str) -> Result<Value, ApiError> {
+    self.get_json(&["user", &format!("{name}.json")]).await
+  }
+
+  async fn max_item(&self) -> Result<u32, ApiError> {
+    self.get_json(&["maxitem.json"]).await
+  }
+
+  fn is_live(&self) -> bool {
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn urls_are_built_under_the_base_url() {
+    let backend = FirebaseBackend::with_base_url("http://localhost:8080/v0");
+    assert_eq!(backend.url(&["item", "8863.json"]).unwrap().as_str(), "http://localhost:8080/v0/item/8863.json");
+    assert_eq!(backend.url(&["maxitem.json"]).unwrap().as_str(), "http://localhost:8080/v0/maxitem.json");
+  }
+
+  #[test]
+  fn usernames_are_percent_encoded() {
+    let backend = FirebaseBackend::new();
+    assert_eq!(
+      backend.url(&["user", "a b/../c?d#e.json"]).unwrap().as_str(),
+      "https://hacker-news.firebaseio.com/v0/user/a%20b%2F..%2Fc%3Fd%23e.json"
+    );
+  }
+}
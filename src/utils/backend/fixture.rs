@@ -0,0 +1,116 @@
+//! An in-memory backend serving a fixed set of lists, items and users.
+
+use super::HnBackend;
+use crate::utils::api::StoryListType;
+use crate::utils::error::ApiError;
+use async_trait::async_trait;
+use jiff::Timestamp;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct FixtureBackend {
+  lists: HashMap<StoryListType, Vec<u32>>,
+  items: HashMap<u32, Value>,
+  users: HashMap<String, Value>,
+}
+
+impl FixtureBackend {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_list(mut self, list_type: StoryListType, ids: Vec<u32>) -> Self {
+    self.lists.insert(list_type, ids);
+    self
+  }
+
+  /// Adds an item, keyed by its `id` field.
+  pub fn with_item(mut self, item: Value) -> Self {
+    if let Some(id) = item["id"].as_u64() {
+      self.items.insert(id as u32, item);
+    }
+    self
+  }
+
+  /// Adds a user profile, keyed by its `id` field.
+  pub fn with_user(mut self, user: Value) -> Self {
+    if let Some(name) = user["id"].as_str() {
+      self.users.insert(name.to_string(), user);
+    }
+    self
+  }
+
+  /// A small, self-consistent data set covering every kind of list, for demos.
+  pub fn demo() -> Self {
+    let now = Timestamp::now().as_second();
+    let ago = |minutes: i64| now - minutes * 60;
+    Self::new()
+      .with_list(StoryListType::Best, vec![1, 2, 3, 5])
+      .with_list(StoryListType::Top, vec![2, 1, 5, 3])
+      .with_list(StoryListType::New, vec![5, 3, 2, 1])
+      .with_list(StoryListType::Ask, vec![2])
+      .with_list(StoryListType::Show, vec![3])
+      .with_list(StoryListType::Job, vec![4])
+      .with_item(json!({
+        "id": 1, "type": "story", "by": "alice", "time": ago(90), "score": 128, "descendants": 3,
+        "title": "A fixture story with a short discussion", "url": "https://example.com/fixture", "kids": [10, 11]
+      }))
+      .with_item(json!({
+        "id": 2, "type": "story", "by": "bob", "time": ago(45), "score": 42, "descendants": 0,
+        "title": "Ask HN: How do you test desktop apps?",
+        "text": "We are looking for ways to drive our UI <i>without</i> a network connection."
+      }))
+      .with_item(json!({
+        "id": 3, "type": "story", "by": "carol", "time": ago(10), "score": 7, "descendants": 0,
+        "title": "Show HN: A Hacker News client written with Freya", "url": "https://example.com/show"
+      }))
+      .with_item(json!({
+        "id": 4, "type": "job", "by": "dave", "time": ago(300), "score": 1,
+        "title": "Example Corp is hiring Rust engineers", "url": "https://example.com/jobs",
+        "text": "We build <i>fast</i> desktop software. Remote friendly."
+      }))
+      .with_item(json!({
+        "id": 5, "type": "poll", "by": "erin", "time": ago(20), "score": 31, "descendants": 0,
+        "title": "Poll: Which GUI toolkit do you use for Rust?", "parts": [20, 21, 22]
+      }))
+      .with_item(json!({ "id": 20, "type": "pollopt", "poll": 5, "by": "erin", "text": "Freya", "score": 14 }))
+      .with_item(json!({ "id": 21, "type": "pollopt", "poll": 5, "by": "erin", "text": "egui", "score": 11 }))
+      .with_item(json!({ "id": 22, "type": "pollopt", "poll": 5, "by": "erin", "text": "Iced", "score": 6 }))
+      .with_item(json!({
+        "id": 10, "type": "comment", "by": "bob", "parent": 1, "time": ago(80), "kids": [12],
+        "text": "This is a top-level comment.<p>It has <i>two</i> paragraphs."
+      }))
+      .with_item(json!({
+        "id": 11, "type": "comment", "by": "carol", "parent": 1, "time": ago(70),
+        "text": "Another top-level comment with a <a href=\"https://example.com\">link</a>."
+      }))
+      .with_item(json!({
+        "id": 12, "type": "comment", "by": "alice", "parent": 10, "time": ago(60),
+        "text": "A reply to the first comment."
+      }))
+      .with_user(json!({
+        "id": "alice", "created": ago(60 * 24 * 900), "karma": 4321,
+        "about": "Fixture user.", "submitted": [12, 1]
+      }))
+  }
+}
+
+#[async_trait]
+impl HnBackend for FixtureBackend {
+  async fn story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, ApiError> {
+    Ok(self.lists.get(&list_type).cloned().unwrap_or_default())
+  }
+
+  async fn item(&self, id: u32) -> Result<Value, ApiError> {
+    Ok(self.items.get(&id).cloned().unwrap_or(Value::Null))
+  }
+
+  async fn user(&self, name: &str) -> Result<Value, ApiError> {
+    Ok(self.users.get(name).cloned().unwrap_or(Value::Null))
+  }
+
+  async fn max_item(&self) -> Result<u32, ApiError> {
+    Ok(self.items.keys().copied().max().unwrap_or(0))
+  }
+}
@@ -0,0 +1,72 @@
+//! Interchangeable sources of Hacker News data.
+//!
+//! `ApiService` never talks to the network directly; it asks an `HnBackend` for raw
+//! JSON values and layers caching and offline handling on top. Besides the live
+//! Firebase API there is an in-memory fixture backend for demos and a backend that
+//! replays a previously recorded session, so the UI can run without network access.
+
+mod firebase;
+mod fixture;
+mod replay;
+
+pub use firebase::FirebaseBackend;
+pub use fixture::FixtureBackend;
+pub use replay::{RecordingBackend, ReplayBackend};
+
+use crate::utils::api::StoryListType;
+use crate::utils::error::ApiError;
+use async_trait::async_trait;
+use log::{error, info};
+use serde_json::Value;
+use std::env;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait HnBackend: Send + Sync {
+  /// Returns the ids of the stories currently in the given list.
+  async fn story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, ApiError>;
+  /// Returns the raw JSON of an item, or `Value::Null` if it does not exist.
+  async fn item(&self, id: u32) -> Result<Value, ApiError>;
+  /// Returns the raw JSON of a user profile, or `Value::Null` if it does not exist.
+  async fn user(&self, name: &str) -> Result<Value, ApiError>;
+  /// Returns the largest item id known to the backend.
+  async fn max_item(&self) -> Result<u32, ApiError>;
+
+  /// Whether the backend serves live data that is worth persisting in the item cache.
+  fn is_live(&self) -> bool {
+    false
+  }
+}
+
+/// Selects the backend from the `HN_BACKEND` environment variable:
+///
+/// - unset or `firebase`: the live API (its base URL can be overridden with `HN_API_BASE_URL`)
+/// - `fixture`: the built-in demo data set
+/// - `record:<path>`: the live API, saving every response to `<path>`
+/// - `replay:<path>`: the responses previously recorded to `<path>`
+pub fn from_env() -> Arc<dyn HnBackend> {
+  let firebase = || match env::var("HN_API_BASE_URL") {
+    Ok(base_url) => FirebaseBackend::with_base_url(base_url),
+    Err(_) => FirebaseBackend::new(),
+  };
+  let spec = env::var("HN_BACKEND").unwrap_or_default();
+  let backend: Arc<dyn HnBackend> = match spec.split_once(':') {
+    Some(("record", path)) => Arc::new(RecordingBackend::new(firebase(), path)),
+    Some(("replay", path)) => match ReplayBackend::load(path) {
+      Ok(backend) => Arc::new(backend),
+      Err(e) => {
+        error!("Could not load recording {path}: {e}; falling back to the live API.");
+        Arc::new(firebase())
+      }
+    },
+    _ if spec == "fixture" => Arc::new(FixtureBackend::demo()),
+    _ => {
+      if !spec.is_empty() && spec != "firebase" {
+        error!("Unknown HN_BACKEND value {spec:?}; using the live API.");
+      }
+      Arc::new(firebase())
+    }
+  };
+  info!("Using {} backend.", if spec.is_empty() { "firebase" } else { &spec });
+  backend
+}
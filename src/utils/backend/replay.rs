@@ -0,0 +1,247 @@
+//! Recording of live API responses to a file, one JSON line per response, and
+//! replaying them later.
+
+use super::HnBackend;
+use crate::utils::api::StoryListType;
+use crate::utils::error::ApiError;
+use async_trait::async_trait;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+// Everything a session received from the API.
+#[derive(Default)]
+struct Recording {
+  lists: BTreeMap<String, Vec<u32>>,
+  items: BTreeMap<u32, Value>,
+  users: BTreeMap<String, Value>,
+  max_item: Option<u32>,
+}
+
+// One response, as a line of the recording file.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Response {
+  List { list: String, ids: Vec<u32> },
+  Item { id: u32, item: Value },
+  User { name: String, user: Value },
+  MaxItem(u32),
+}
+
+impl Recording {
+  // Reads a recording file with one response per line; later responses to the same
+  // request replace earlier ones.
+  fn load(path: &Path) -> Result<Self, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut recording = Self::default();
+    for (number, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+      let response = serde_json::from_str(line).map_err(|e| format!("line {}: {e}", number + 1))?;
+      recording.apply(response);
+    }
+    Ok(recording)
+  }
+
+  fn apply(&mut self, response: Response) {
+    match response {
+      Response::List { list, ids } => _ = self.lists.insert(list, ids),
+      Response::Item { id, item } => _ = self.items.insert(id, item),
+      Response::User { name, user } => _ = self.users.insert(name, user),
+      Response::MaxItem(max_item) => self.max_item = Some(max_item),
+    }
+  }
+}
+
+/// Serves only what was recorded; anything else is reported as an error.
+pub struct ReplayBackend {
+  recording: Recording,
+}
+
+impl ReplayBackend {
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+    Ok(Self { recording: Recording::load(path.as_ref())? })
+  }
+}
+
+#[async_trait]
+impl HnBackend for ReplayBackend {
+  async fn story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, ApiError> {
+    self
+      .recording
+      .lists
+      .get(list_type.as_api_str())
+      .cloned()
+      .ok_or_else(|| ApiError::Unavailable(format!("the {list_type} list was not recorded")))
+  }
+
+  async fn item(&self, id: u32) -> Result<Value, ApiError> {
+    self.recording.items.get(&id).cloned().ok_or_else(|| ApiError::Unavailable(format!("item {id} was not recorded")))
+  }
+
+  async fn user(&self, name: &str) -> Result<Value, ApiError> {
+    self
+      .recording
+      .users
+      .get(name)
+      .cloned()
+      .ok_or_else(|| ApiError::Unavailable(format!("user {name} was not recorded")))
+  }
+
+  async fn max_item(&self) -> Result<u32, ApiError> {
+    self.recording.max_item.ok_or_else(|| ApiError::Unavailable("the max item was not recorded".to_string()))
+  }
+}
+
+/// Forwards every request to another backend and appends its responses to a recording file.
+pub struct RecordingBackend<B> {
+  inner: B,
+  path: PathBuf,
+  // Held while a line is appended, so that concurrent responses cannot interleave.
+  file: Mutex<()>,
+}
+
+impl<B: HnBackend> RecordingBackend<B> {
+  /// Starts recording to `path`, extending the recording already stored there, if any.
+  pub fn new(inner: B, path: impl Into<PathBuf>) -> Self {
+    Self { inner, path: path.into(), file: Mutex::new(()) }
+  }
+
+  // Appends one response to the recording file.
+  async fn record(&self, response: Response) {
+    let _guard = self.file.lock().await;
+    let result = match serde_json::to_string(&response) {
+      Ok(mut line) => {
+        line.push('\n');
+        append(&self.path, line.as_bytes()).await
+      }
+      Err(e) => Err(e.into()),
+    };
+    if let Err(e) = result {
+      warn!("Failed to save recording {}: {}", self.path.display(), e);
+    }
+  }
+}
+
+async fn append(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+  let mut file = OpenOptions::new().create(true).append(true).open(path).await?;
+  file.write_all(bytes).await?;
+  // Tokio writes in the background; flushing waits until the line is in the file.
+  file.flush().await
+}
+
+#[async_trait]
+impl<B: HnBackend> HnBackend for RecordingBackend<B> {
+  async fn story_ids(&self, list_type: StoryListType) -> Result<Vec<u32>, ApiError> {
+    let ids = self.inner.story_ids(list_type).await?;
+    self.record(Response::List { list: list_type.as_api_str().to_string(), ids: ids.clone() }).await;
+    Ok(ids)
+  }
+
+  async fn item(&self, id: u32) -> Result<Value, ApiError> {
+    let item = self.inner.item(id).await?;
+    self.record(Response::Item { id, item: item.clone() }).await;
+    Ok(item)
+  }
+
+  async fn user(&self, name: &str) -> Result<Value, ApiError> {
+    let user = self.inner.user(name).await?;
+    self.record(Response::User { name: name.to_string(), user: user.clone() }).await;
+    Ok(user)
+  }
+
+  async fn max_item(&self) -> Result<u32, ApiError> {
+    let max_item = self.inner.max_item().await?;
+    self.record(Response::MaxItem(max_item)).await;
+    Ok(max_item)
+  }
+
+  fn is_live(&self) -> bool {
+    self.inner.is_live()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::backend::FixtureBackend;
+  use serde_json::json;
+
+  fn fixture() -> FixtureBackend {
+    FixtureBackend::new()
+      .with_list(StoryListType::Top, vec![1])
+      .with_item(json!({ "id": 1, "type": "story", "title": "Recorded" }))
+      .with_user(json!({ "id": "alice", "karma": 1 }))
+  }
+
+  #[tokio::test]
+  async fn a_recorded_session_replays() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    let recorder = RecordingBackend::new(fixture(), &path);
+    recorder.story_ids(StoryListType::Top).await.unwrap();
+    recorder.item(1).await.unwrap();
+    recorder.item(2).await.unwrap();
+    recorder.user("alice").await.unwrap();
+    recorder.max_item().await.unwrap();
+
+    let replay = ReplayBackend::load(&path).unwrap();
+    assert_eq!(replay.story_ids(StoryListType::Top).await, Ok(vec![1]));
+    assert_eq!(replay.item(1).await.unwrap()["title"], "Recorded");
+    // Missing items are recorded as the `null` the API answered.
+    assert_eq!(replay.item(2).await, Ok(Value::Null));
+    assert_eq!(replay.user("alice").await.unwrap()["karma"], 1);
+    assert_eq!(replay.max_item().await, Ok(1));
+  }
+
+  #[tokio::test]
+  async fn recording_appends_to_an_existing_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    RecordingBackend::new(fixture(), &path).item(1).await.unwrap();
+    RecordingBackend::new(fixture(), &path).story_ids(StoryListType::Top).await.unwrap();
+
+    let replay = ReplayBackend::load(&path).unwrap();
+    assert!(replay.item(1).await.is_ok());
+    assert!(replay.story_ids(StoryListType::Top).await.is_ok());
+  }
+
+  #[tokio::test]
+  async fn later_responses_replace_earlier_ones() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    std::fs::write(
+      &path,
+      "{\"list\":{\"list\":\"topstories\",\"ids\":[1]}}\n\n{\"list\":{\"list\":\"topstories\",\"ids\":[2, 1]}}\n",
+    )
+    .unwrap();
+
+    let replay = ReplayBackend::load(&path).unwrap();
+    assert_eq!(replay.story_ids(StoryListType::Top).await, Ok(vec![2, 1]));
+  }
+
+  #[tokio::test]
+  async fn anything_not_recorded_is_unavailable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    std::fs::write(&path, "").unwrap();
+
+    let replay = ReplayBackend::load(&path).unwrap();
+    assert!(matches!(replay.story_ids(StoryListType::Best).await, Err(ApiError::Unavailable(_))));
+    assert!(matches!(replay.item(1).await, Err(ApiError::Unavailable(_))));
+    assert!(matches!(replay.user("bob").await, Err(ApiError::Unavailable(_))));
+  }
+
+  #[test]
+  fn a_corrupt_line_fails_to_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    std::fs::write(&path, "{\"max_item\":3}\nnot json\n").unwrap();
+
+    let error = ReplayBackend::load(&path).err().unwrap();
+    assert!(error.starts_with("line 2:"), "{error}");
+  }
+}
@@ -0,0 +1,173 @@
+//! Stories and comments saved for later, with the user's tags and notes, kept
+//! between sessions. Items are stored as they were when saved, so that the saved
+//! list shows without fetching anything.
+
+use crate::models::{Comment, Story};
+use crate::utils::store;
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+const FILE_NAME: &str = "bookmarks.json";
+
+/// What is kept of a saved comment.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SavedComment {
+  pub id: u32,
+  pub by: Option<String>,
+  /// The comment's HTML.
+  pub text: Option<String>,
+  #[serde(default, with = "jiff::fmt::serde::timestamp::second::optional")]
+  pub time: Option<Timestamp>,
+  /// The comment or story it replies to.
+  pub parent: Option<u32>,
+}
+
+impl From<&Comment> for SavedComment {
+  fn from(comment: &Comment) -> Self {
+    Self {
+      id: comment.id,
+      by: comment.by.clone(),
+      text: comment.text.clone(),
+      time: comment.time,
+      parent: comment.parent,
+    }
+  }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SavedItem {
+  Story(Story),
+  Comment(SavedComment),
+}
+
+impl SavedItem {
+  pub fn id(&self) -> u32 {
+    match self {
+      SavedItem::Story(story) => story.id,
+      SavedItem::Comment(comment) => comment.id,
+    }
+  }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Bookmark {
+  pub item: SavedItem,
+  pub saved_at: Timestamp,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  #[serde(default)]
+  pub note: String,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bookmarks {
+  // Most recently saved first.
+  items: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+  /// Reads the bookmarks from the data directory.
+  pub fn load() -> Self {
+    store::load(FILE_NAME)
+  }
+
+  pub fn items(&self) -> &[Bookmark] {
+    &self.items
+  }
+
+  pub fn is_saved(&self, id: u32) -> bool {
+    self.items.iter().any(|bookmark| bookmark.item.id() == id)
+  }
+
+  /// Every tag in use, sorted.
+  pub fn tags(&self) -> Vec<String> {
+    let mut tags: Vec<String> = self.items.iter().flat_map(|bookmark| bookmark.tags.iter().cloned()).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+  }
+
+  /// Saves `item`, or removes it if it is saved already.
+  pub fn toggle(&mut self, item: SavedItem) {
+    let id = item.id();
+    if self.is_saved(id) {
+      self.items.retain(|bookmark| bookmark.item.id() != id);
+    } else {
+      self.items.insert(0, Bookmark { item, saved_at: Timestamp::now(), tags: Vec::new(), note: String::new() });
+    }
+    self.save();
+  }
+
+  /// Replaces the tags and note of a saved item. Tags are trimmed, and empty or
+  /// repeated ones dropped.
+  pub fn annotate(&mut self, id: u32, tags: Vec<String>, note: String) {
+    let Some(bookmark) = self.items.iter_mut().find(|bookmark| bookmark.item.id() == id) else { return };
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()) {
+      if !cleaned.iter().any(|existing| existing == tag) {
+        cleaned.push(tag.to_string());
+      }
+    }
+    bookmark.tags = cleaned;
+    bookmark.note = note.trim().to_string();
+    self.save();
+  }
+
+  fn save(&self) {
+    store::save_later(FILE_NAME, self.clone());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn saved(ids: &[u32]) -> Bookmarks {
+    let mut bookmarks = Bookmarks::default();
+    for &id in ids {
+      bookmarks.toggle(SavedItem::Story(Story { id, ..Story::default() }));
+    }
+    bookmarks
+  }
+
+  fn tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(ToString::to_string).collect()
+  }
+
+  #[test]
+  fn annotations_are_trimmed_and_deduplicated() {
+    let mut bookmarks = saved(&[1]);
+    bookmarks.annotate(1, tags(&[" rust ", "", "gui", "rust", "  "]), "  read later \n".to_string());
+    let bookmark = &bookmarks.items()[0];
+    assert_eq!(bookmark.tags, ["rust", "gui"]);
+    assert_eq!(bookmark.note, "read later");
+  }
+
+  #[test]
+  fn annotating_replaces_the_previous_annotation() {
+    let mut bookmarks = saved(&[1]);
+    bookmarks.annotate(1, tags(&["rust"]), "note".to_string());
+    bookmarks.annotate(1, Vec::new(), String::new());
+    assert!(bookmarks.items()[0].tags.is_empty());
+    assert_eq!(bookmarks.items()[0].note, "");
+  }
+
+  #[test]
+  fn annotating_only_touches_the_given_item() {
+    let mut bookmarks = saved(&[1, 2]);
+    bookmarks.annotate(1, tags(&["b"]), String::new());
+    bookmarks.annotate(2, tags(&["a", "b"]), String::new());
+    bookmarks.annotate(3, tags(&["unsaved"]), String::new());
+    assert_eq!(bookmarks.tags(), ["a", "b"]);
+    assert!(!bookmarks.is_saved(3));
+  }
+
+  #[test]
+  fn toggling_saves_newest_first_and_unsaves() {
+    let mut bookmarks = saved(&[1, 2]);
+    assert_eq!(bookmarks.items().iter().map(|bookmark| bookmark.item.id()).collect::<Vec<_>>(), [2, 1]);
+    bookmarks.toggle(SavedItem::Story(Story { id: 1, ..Story::default() }));
+    assert!(!bookmarks.is_saved(1) && bookmarks.is_saved(2));
+  }
+}
@@ -0,0 +1,228 @@
+//! A persistent on-disk cache of raw Hacker News items, keyed by item id.
+//!
+//! Every item is stored as its own JSON file together with the time it was fetched,
+//! so the `ApiService` can decide per item whether the copy is still fresh enough
+//! to be served without touching the network. The last known id list of every
+//! story list is kept alongside the items so the app can be browsed offline.
+
+use crate::utils::api::StoryListType;
+use crate::utils::paths;
+use jiff::{SignedDuration, Timestamp};
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+// Cached items not refreshed for this long are deleted at startup.
+const PRUNE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+// The most items kept on disk; the least recently fetched beyond it are deleted.
+const PRUNE_MAX_ITEMS: usize = 20_000;
+
+// An item as it is persisted on disk.
+#[derive(Serialize, Deserialize)]
+pub struct CachedItem {
+  pub fetched_at: Timestamp,
+  pub item: Value,
+}
+
+impl CachedItem {
+  /// Returns `true` if the item was fetched less than `max_age` ago.
+  pub fn is_fresh(&self, max_age: SignedDuration) -> bool {
+    Timestamp::now().duration_since(self.fetched_at) < max_age
+  }
+}
+
+// A story id list as it is persisted on disk.
+#[derive(Serialize, Deserialize)]
+pub struct CachedList {
+  pub fetched_at: Timestamp,
+  pub ids: Vec<u32>,
+}
+
+#[derive(Clone)]
+pub struct ItemCache {
+  // `None` when no cache directory could be resolved or created; the cache then
+  // silently behaves as if it were always empty.
+  root: Option<PathBuf>,
+}
+
+impl ItemCache {
+  /// The cache in the user's cache directory. Old items are pruned in the background.
+  pub fn new() -> Self {
+    let cache = paths::cache_dir().map_or_else(Self::disabled, Self::in_dir);
+    if let Some(items) = cache.root.as_ref().map(|dir| dir.join("items")) {
+      std::thread::spawn(move || prune(&items, PRUNE_MAX_AGE, PRUNE_MAX_ITEMS));
+    }
+    cache
+  }
+
+  /// The cache stored under `dir`, or a disabled one if it cannot be created.
+  pub fn in_dir(dir: PathBuf) -> Self {
+    match std::fs::create_dir_all(dir.join("items")).and_then(|_| std::fs::create_dir_all(dir.join("lists"))) {
+      Ok(()) => Self { root: Some(dir) },
+      Err(e) => {
+        warn!("Item cache disabled, could not create {}: {}", dir.display(), e);
+        Self::disabled()
+      }
+    }
+  }
+
+  /// A cache that never stores anything.
+  pub fn disabled() -> Self {
+    Self { root: None }
+  }
+
+  fn entry_path(&self, kind: &str, key: &str) -> Option<PathBuf> {
+    self.root.as_ref().map(|dir| dir.join(kind).join(format!("{key}.json")))
+  }
+
+  /// Reads an item from the cache, returning `None` if it is missing or unreadable.
+  pub async fn get(&self, id: u32) -> Option<CachedItem> {
+    read_entry(self.entry_path("items", &id.to_string())?).await
+  }
+
+  /// Stores an item, stamped with the current time. Failures are logged and otherwise ignored.
+  pub async fn put(&self, id: u32, item: &Value) {
+    let Some(path) = self.entry_path("items", &id.to_string()) else { return };
+    write_entry(path, &CachedItem { fetched_at: Timestamp::now(), item: item.clone() }).await;
+  }
+
+  /// Reads the last known id list for a story list type.
+  pub async fn get_list(&self, list_type: StoryListType) -> Option<CachedList> {
+    read_entry(self.entry_path("lists", list_type.as_api_str())?).await
+  }
+
+  /// Stores the id list for a story list type, stamped with the current time.
+  pub async fn put_list(&self, list_type: StoryListType, ids: &[u32]) {
+    let Some(path) = self.entry_path("lists", list_type.as_api_str()) else { return };
+    write_entry(path, &CachedList { fetched_at: Timestamp::now(), ids: ids.to_vec() }).await;
+  }
+}
+
+/// Deletes the item files in `dir` last written more than `max_age` ago, then the
+/// oldest ones until at most `max_items` are left. Returns how many were deleted.
+pub fn prune(dir: &Path, max_age: Duration, max_items: usize) -> usize {
+  let entries = match std::fs::read_dir(dir) {
+    Ok(entries) => entries,
+    Err(e) => {
+      warn!("Could not prune the item cache in {}: {}", dir.display(), e);
+      return 0;
+    }
+  };
+  let mut files: Vec<(SystemTime, PathBuf)> = entries
+    .filter_map(Result::ok)
+    .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+    .collect();
+  // Newest first, so that everything past the cut is either too old or too many.
+  files.sort_unstable_by_key(|(modified, _)| Reverse(*modified));
+  let now = SystemTime::now();
+  let keep = files
+    .iter()
+    .take(max_items)
+    .take_while(|(modified, _)| now.duration_since(*modified).unwrap_or_default() <= max_age)
+    .count();
+  let mut deleted = 0;
+  for (_, path) in &files[keep..] {
+    match std::fs::remove_file(path) {
+      Ok(()) => deleted += 1,
+      Err(e) => warn!("Could not delete cache entry {}: {}", path.display(), e),
+    }
+  }
+  if deleted > 0 {
+    info!("Pruned {deleted} items from the cache");
+  }
+  deleted
+}
+
+async fn read_entry<T: DeserializeOwned>(path: PathBuf) -> Option<T> {
+  let bytes = tokio::fs::read(&path).await.ok()?;
+  match serde_json::from_slice(&bytes) {
+    Ok(entry) => Some(entry),
+    Err(e) => {
+      warn!("Discarding corrupt cache entry {}: {}", path.display(), e);
+      None
+    }
+  }
+}
+
+async fn write_entry<T: Serialize>(path: PathBuf, entry: &T) {
+  let result = match serde_json::to_vec(entry) {
+    Ok(bytes) => tokio::fs::write(&path, bytes).await,
+    Err(e) => Err(e.into()),
+  };
+  if let Err(e) = result {
+    warn!("Failed to write cache entry {}: {}", path.display(), e);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs::File;
+
+  const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+  // Writes an item file last modified `age` ago.
+  fn write_aged(dir: &Path, id: u32, age: Duration) {
+    let file = File::create(dir.join(format!("{id}.json"))).unwrap();
+    file.set_modified(SystemTime::now() - age).unwrap();
+  }
+
+  fn remaining(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> =
+      std::fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    names.sort();
+    names
+  }
+
+  #[test]
+  fn prune_deletes_items_older_than_max_age() {
+    let dir = tempfile::tempdir().unwrap();
+    write_aged(dir.path(), 1, DAY);
+    write_aged(dir.path(), 2, 40 * DAY);
+
+    assert_eq!(prune(dir.path(), 30 * DAY, 100), 1);
+    assert_eq!(remaining(dir.path()), ["1.json"]);
+  }
+
+  #[test]
+  fn prune_keeps_the_most_recent_items_up_to_max_items() {
+    let dir = tempfile::tempdir().unwrap();
+    for id in 1..=5 {
+      write_aged(dir.path(), id, id * DAY);
+    }
+
+    assert_eq!(prune(dir.path(), 30 * DAY, 3), 2);
+    assert_eq!(remaining(dir.path()), ["1.json", "2.json", "3.json"]);
+  }
+
+  #[test]
+  fn prune_of_a_missing_directory_deletes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(prune(&dir.path().join("missing"), DAY, 1), 0);
+  }
+
+  #[tokio::test]
+  async fn items_and_lists_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ItemCache::in_dir(dir.path().to_path_buf());
+    cache.put(7, &serde_json::json!({ "id": 7 })).await;
+    cache.put_list(StoryListType::Top, &[7, 8]).await;
+
+    let item = cache.get(7).await.unwrap();
+    assert_eq!(item.item["id"], 7);
+    assert!(item.is_fresh(SignedDuration::from_mins(1)));
+    assert_eq!(cache.get_list(StoryListType::Top).await.unwrap().ids, [7, 8]);
+    assert!(cache.get(8).await.is_none());
+  }
+
+  #[tokio::test]
+  async fn a_disabled_cache_stores_nothing() {
+    let cache = ItemCache::disabled();
+    cache.put(7, &serde_json::json!({ "id": 7 })).await;
+    assert!(cache.get(7).await.is_none());
+  }
+}
@@ -4,6 +4,35 @@ use jiff::{
   tz,
 };
 
+/// The day a timestamp falls on in the system's time zone.
+pub fn local_date(ts: &Timestamp) -> Date {
+  ts.to_zoned(tz::TimeZone::system()).date()
+}
+
+/// An optional day typed into a date field as `YYYY-MM-DD`; an empty field leaves
+/// that end of a range open.
+pub fn parse_date(value: &str) -> Result<Option<Date>, jiff::Error> {
+  let value = value.trim();
+  if value.is_empty() { Ok(None) } else { value.parse().map(Some) }
+}
+
+/// A day as a heading, e.g. "Today" or "Monday, October 12, 2026".
+pub fn format_day(date: Date) -> String {
+  let today = local_date(&Timestamp::now());
+  if date == today {
+    "Today".to_string()
+  } else if today.yesterday().is_ok_and(|yesterday| date == yesterday) {
+    "Yesterday".to_string()
+  } else {
+    date.strftime("%A, %B %-d, %Y").to_string()
+  }
+}
+
+/// The time of day of a timestamp in the system's time zone, e.g. "14:05".
+pub fn format_time_of_day(ts: &Timestamp) -> String {
+  ts.to_zoned(tz::TimeZone::system()).strftime("%H:%M").to_string()
+}
+
 pub fn format_timestamp(ts: &Timestamp) -> String {
   let now_ms = Timestamp::now().as_millisecond();
   let ts_ms = ts.as_millisecond();
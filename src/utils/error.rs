@@ -0,0 +1,107 @@
+//! The error type returned by the API layer.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+  /// The request did not complete in time.
+  Timeout,
+  /// The server could not be reached at all (DNS, refused connection, TLS, ...).
+  Network(String),
+  /// The server answered with a non-success status code.
+  Http(u16),
+  /// The response was not the JSON we expected.
+  Decode(String),
+  /// The item or user does not exist; the API answers `null` for those.
+  NotFound,
+  /// The backend cannot serve the request, e.g. it is missing from a recording.
+  Unavailable(String),
+}
+
+impl ApiError {
+  /// Whether repeating the same request may succeed.
+  pub fn is_retryable(&self) -> bool {
+    match self {
+      Self::Timeout | Self::Network(_) => true,
+      Self::Http(status) => *status == 429 || *status >= 500,
+      Self::Decode(_) | Self::NotFound | Self::Unavailable(_) => false,
+    }
+  }
+
+  /// Whether the error means the API could not be reached, as opposed to answering badly.
+  pub fn is_connection_error(&self) -> bool {
+    matches!(self, Self::Timeout | Self::Network(_))
+  }
+}
+
+impl fmt::Display for ApiError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Timeout => write!(f, "the request timed out"),
+      Self::Network(msg) => write!(f, "could not reach Hacker News ({msg})"),
+      Self::Http(status) => write!(f, "the server responded with HTTP {status}"),
+      Self::Decode(msg) => write!(f, "unexpected response from the server ({msg})"),
+      Self::NotFound => write!(f, "the item was deleted or does not exist"),
+      Self::Unavailable(msg) => write!(f, "{msg}"),
+    }
+  }
+}
+
+impl From<reqwest::Error> for ApiError {
+  fn from(e: reqwest::Error) -> Self {
+    if e.is_timeout() {
+      Self::Timeout
+    } else if let Some(status) = e.status() {
+      Self::Http(status.as_u16())
+    } else if e.is_decode() {
+      Self::Decode(e.to_string())
+    } else {
+      Self::Network(e.to_string())
+    }
+  }
+}
+
+impl From<serde_json::Error> for ApiError {
+  fn from(e: serde_json::Error) -> Self {
+    Self::Decode(e.to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn transient_failures_are_retryable() {
+    for error in [ApiError::Timeout, ApiError::Network("reset".to_string()), ApiError::Http(429), ApiError::Http(503)] {
+      assert!(error.is_retryable(), "{error:?}");
+    }
+  }
+
+  #[test]
+  fn answers_that_will_not_change_are_not_retryable() {
+    for error in [
+      ApiError::Http(404),
+      ApiError::Http(400),
+      ApiError::Decode("bad".to_string()),
+      ApiError::NotFound,
+      ApiError::Unavailable("not recorded".to_string()),
+    ] {
+      assert!(!error.is_retryable(), "{error:?}");
+    }
+  }
+
+  #[test]
+  fn only_unreachable_servers_are_connection_errors() {
+    assert!(ApiError::Timeout.is_connection_error());
+    assert!(ApiError::Network("refused".to_string()).is_connection_error());
+    assert!(!ApiError::Http(503).is_connection_error());
+    assert!(!ApiError::NotFound.is_connection_error());
+  }
+
+  #[test]
+  fn decode_errors_convert_from_serde() {
+    let error: ApiError = serde_json::from_str::<u32>("\"x\"").unwrap_err().into();
+    assert!(matches!(error, ApiError::Decode(_)));
+  }
+}
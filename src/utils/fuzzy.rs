@@ -0,0 +1,68 @@
+//! Fuzzy matching of typed queries against short titles, as used by the command
+//! palette: the query's characters must appear in order, and matches at word
+//! starts and runs of consecutive characters rank higher.
+
+const MATCH_SCORE: i32 = 1;
+const WORD_START_BONUS: i32 = 8;
+const CONSECUTIVE_BONUS: i32 = 4;
+// Subtracted per skipped character, so tighter matches win ties.
+const GAP_PENALTY: i32 = 1;
+
+/// Scores how well `query` matches `candidate`, ignoring case and whitespace in
+/// the query. `None` means it does not match; an empty query matches everything
+/// with a score of zero.
+pub fn score(query: &str, candidate: &str) -> Option<i32> {
+  let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+  let mut total = 0;
+  let mut position = 0;
+  let mut previous: Option<usize> = None;
+  for wanted in query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase) {
+    let found = position + candidate[position..].iter().position(|&c| c == wanted)?;
+    total += MATCH_SCORE;
+    if found == 0 || !candidate[found - 1].is_alphanumeric() {
+      total += WORD_START_BONUS;
+    }
+    match previous {
+      Some(previous) if previous + 1 == found => total += CONSECUTIVE_BONUS,
+      Some(previous) => total -= GAP_PENALTY * (found - previous - 1) as i32,
+      None => {}
+    }
+    previous = Some(found);
+    position = found + 1;
+  }
+  Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn characters_must_appear_in_order() {
+    assert_eq!(score("xyz", "Refresh"), None);
+    assert_eq!(score("hser", "Refresh"), None);
+    assert_eq!(score("refreshes", "Refresh"), None);
+    assert!(score("rfs", "Refresh").is_some());
+  }
+
+  #[test]
+  fn an_empty_query_matches_everything() {
+    assert_eq!(score("", "Refresh"), Some(0));
+    assert_eq!(score("  ", ""), Some(0));
+  }
+
+  #[test]
+  fn case_and_whitespace_in_the_query_are_ignored() {
+    assert_eq!(score("GO BACK", "Go back"), score("goback", "Go back"));
+  }
+
+  #[test]
+  fn word_starts_and_runs_rank_higher() {
+    // Word starts beat the same letters inside words.
+    assert!(score("nt", "Next tab") > score("nt", "Manage content"));
+    // A run of characters beats the same characters scattered.
+    assert!(score("tab", "Next tab") > score("tab", "Toggle light/dark theme bar"));
+    // Fewer skipped characters win otherwise.
+    assert!(score("ab", "a b") > score("ab", "a    b"));
+  }
+}
@@ -0,0 +1,217 @@
+//! Keyword and regular expression rules marking the topics the user follows in
+//! story titles and comments, kept between sessions.
+
+use crate::models::Story;
+use crate::theme::HighlightColor;
+use crate::utils::store;
+use crate::utils::text_rule::TextRule;
+use log::warn;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+const FILE_NAME: &str = "highlights.json";
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HighlightRule {
+  pub rule: TextRule,
+  pub color: HighlightColor,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Highlights {
+  rules: Vec<HighlightRule>,
+  only_highlighted: bool,
+  // The rules, compiled, with their colors.
+  #[serde(skip)]
+  patterns: Vec<(Regex, HighlightColor)>,
+}
+
+impl Highlights {
+  /// Reads the rules from the data directory. Patterns that no longer compile are
+  /// kept but match nothing.
+  pub fn load() -> Self {
+    let mut highlights: Self = store::load(FILE_NAME);
+    highlights.compile_patterns();
+    highlights
+  }
+
+  pub fn rules(&self) -> &[HighlightRule] {
+    &self.rules
+  }
+
+  /// Whether lists show only the stories with a highlighted title.
+  pub fn only_highlighted(&self) -> bool {
+    self.only_highlighted
+  }
+
+  pub fn is_highlighted(&self, text: &str) -> bool {
+    self.patterns.iter().any(|(pattern, _)| pattern.is_match(text))
+  }
+
+  /// Whether lists leave out `story` for not having a highlighted title. Without
+  /// rules nothing is left out.
+  pub fn hides(&self, story: &Story) -> bool {
+    self.only_highlighted
+      && !self.patterns.is_empty()
+      && !story.title.as_deref().is_some_and(|title| self.is_highlighted(title))
+  }
+
+  /// Splits `text` into runs that are either highlighted, with their color, or
+  /// not. Where matches overlap, the one starting first wins, then the earlier rule.
+  pub fn runs<'a>(&self, text: &'a str) -> Vec<(&'a str, Option<HighlightColor>)> {
+    let mut matches: Vec<(usize, usize, usize, HighlightColor)> = self
+      .patterns
+      .iter()
+      .enumerate()
+      .flat_map(|(index, (pattern, color))| {
+        pattern.find_iter(text).filter(|m| !m.is_empty()).map(move |m| (m.start(), index, m.end(), *color))
+      })
+      .collect();
+    matches.sort_by_key(|&(start, index, ..)| (start, index));
+    let mut runs = Vec::new();
+    let mut position = 0;
+    for (start, _, end, color) in matches {
+      if start < position {
+        continue;
+      }
+      if start > position {
+        runs.push((&text[position..start], None));
+      }
+      runs.push((&text[start..end], Some(color)));
+      position = end;
+    }
+    if position < text.len() {
+      runs.push((&text[position..], None));
+    }
+    runs
+  }
+
+  /// Adds a rule, unless its pattern is not a valid regular expression.
+  pub fn add(&mut self, rule: TextRule, color: HighlightColor) -> Result<(), regex::Error> {
+    rule.compile()?;
+    if !self.rules.iter().any(|existing| existing.rule == rule) {
+      self.rules.push(HighlightRule { rule, color });
+      self.compile_patterns();
+      self.save();
+    }
+    Ok(())
+  }
+
+  pub fn remove(&mut self, index: usize) {
+    if index < self.rules.len() {
+      self.rules.remove(index);
+      self.compile_patterns();
+      self.save();
+    }
+  }
+
+  pub fn set_only_highlighted(&mut self, only_highlighted: bool) {
+    self.only_highlighted = only_highlighted;
+    self.save();
+  }
+
+  fn compile_patterns(&mut self) {
+    self.patterns = self
+      .rules
+      .iter()
+      .filter_map(|HighlightRule { rule, color }| {
+        let pattern = rule.compile().inspect_err(|e| warn!("Ignoring invalid highlight rule {rule}: {e}")).ok()?;
+        Some((pattern, *color))
+      })
+      .collect();
+  }
+
+  fn save(&self) {
+    store::save_later(FILE_NAME, self.clone());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn highlighting(rules: &[(TextRule, HighlightColor)]) -> Highlights {
+    let mut highlights = Highlights::default();
+    for (rule, color) in rules {
+      highlights.add(rule.clone(), *color).unwrap();
+    }
+    highlights
+  }
+
+  fn keyword(keyword: &str) -> TextRule {
+    TextRule::Keyword(keyword.to_string())
+  }
+
+  fn story(title: Option<&str>) -> Story {
+    Story { id: 1, title: title.map(str::to_string), ..Story::default() }
+  }
+
+  #[test]
+  fn text_without_matches_is_one_plain_run() {
+    let highlights = highlighting(&[(keyword("rust"), HighlightColor::Orange)]);
+    assert_eq!(highlights.runs("Go and Zig"), [("Go and Zig", None)]);
+    assert_eq!(highlights.runs(""), []);
+    assert_eq!(Highlights::default().runs("Rust"), [("Rust", None)]);
+  }
+
+  #[test]
+  fn keywords_are_highlighted_ignoring_case() {
+    let highlights = highlighting(&[(keyword("rust"), HighlightColor::Green)]);
+    assert_eq!(
+      highlights.runs("Rust beats RUST."),
+      [("Rust", Some(HighlightColor::Green)), (" beats ", None), ("RUST", Some(HighlightColor::Green)), (".", None)]
+    );
+    // Keywords match literally, not as patterns.
+    let highlights = highlighting(&[(keyword("c++"), HighlightColor::Blue)]);
+    assert_eq!(highlights.runs("C++ or cc"), [("C++", Some(HighlightColor::Blue)), (" or cc", None)]);
+  }
+
+  #[test]
+  fn regex_matches_are_highlighted() {
+    let highlights = highlighting(&[(TextRule::Regex(r"v\d+(\.\d+)*".to_string()), HighlightColor::Purple)]);
+    assert_eq!(
+      highlights.runs("From v1.2 to v10"),
+      [("From ", None), ("v1.2", Some(HighlightColor::Purple)), (" to ", None), ("v10", Some(HighlightColor::Purple))]
+    );
+    // Empty matches highlight nothing.
+    let highlights = highlighting(&[(TextRule::Regex("x*".to_string()), HighlightColor::Purple)]);
+    assert_eq!(highlights.runs("abc"), [("abc", None)]);
+  }
+
+  #[test]
+  fn overlapping_matches_go_to_the_first_start_then_the_earlier_rule() {
+    let highlights = highlighting(&[
+      (keyword("script"), HighlightColor::Green),
+      (keyword("javascript"), HighlightColor::Orange),
+      (keyword("java"), HighlightColor::Blue),
+    ]);
+    assert_eq!(highlights.runs("javascript"), [("javascript", Some(HighlightColor::Orange))]);
+    let highlights =
+      highlighting(&[(keyword("java"), HighlightColor::Blue), (keyword("javascript"), HighlightColor::Orange)]);
+    assert_eq!(highlights.runs("javascript"), [("java", Some(HighlightColor::Blue)), ("script", None)]);
+    let highlights = highlighting(&[(keyword("ab"), HighlightColor::Green), (keyword("bc"), HighlightColor::Blue)]);
+    assert_eq!(highlights.runs("abc"), [("ab", Some(HighlightColor::Green)), ("c", None)]);
+  }
+
+  #[test]
+  fn invalid_patterns_are_rejected() {
+    let mut highlights = Highlights::default();
+    assert!(highlights.add(TextRule::Regex("(".to_string()), HighlightColor::Orange).is_err());
+    assert!(highlights.rules().is_empty());
+  }
+
+  #[test]
+  fn only_unhighlighted_titles_are_hidden_when_asked() {
+    let mut highlights = highlighting(&[(keyword("rust"), HighlightColor::Orange)]);
+    assert!(!highlights.hides(&story(Some("Go 2.0"))));
+    highlights.set_only_highlighted(true);
+    assert!(highlights.hides(&story(Some("Go 2.0"))));
+    assert!(highlights.hides(&story(None)));
+    assert!(!highlights.hides(&story(Some("Rust 2.0"))));
+    // Without rules nothing is hidden.
+    let mut highlights = Highlights::default();
+    highlights.set_only_highlighted(true);
+    assert!(!highlights.hides(&story(Some("Go 2.0"))));
+  }
+}
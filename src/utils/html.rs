@@ -0,0 +1,275 @@
+//! Parses the small subset of HTML Hacker News uses in comments, self posts and
+//! profiles into blocks of styled text spans.
+//!
+//! HN emits `<p>` as a paragraph separator (never closed), `<i>` for emphasis,
+//! `<a href>` for links and `<pre><code>` for code blocks; quotes are plain
+//! paragraphs starting with `>`. Any other tag is dropped, keeping its text.
+
+/// A run of text sharing the same style.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Span {
+  pub text: String,
+  pub italic: bool,
+  /// The target of the hyperlink this text belongs to.
+  pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+  Paragraph(Vec<Span>),
+  /// A paragraph quoting someone else, with the leading `>` removed.
+  Quote(Vec<Span>),
+  /// Preformatted text, with its whitespace preserved.
+  Code(String),
+}
+
+/// Parses HN's HTML into blocks. Never fails; malformed markup degrades to text.
+pub fn parse(html: &str) -> Vec<Block> {
+  let mut parser = Parser::default();
+  let mut rest = html;
+  while let Some(start) = rest.find('<') {
+    parser.push_text(&rest[..start]);
+    let Some(len) = rest[start..].find('>') else {
+      rest = &rest[start..];
+      break;
+    };
+    parser.handle_tag(&rest[start + 1..start + len]);
+    rest = &rest[start + len + 1..];
+  }
+  parser.push_text(rest);
+  parser.finish()
+}
+
+/// Flattens HN's HTML into plain text, with blank lines between blocks.
+pub fn to_plain_text(html: &str) -> String {
+  let flatten = |spans: &[Span]| spans.iter().map(|span| span.text.as_str()).collect::<String>();
+  parse(html)
+    .iter()
+    .map(|block| match block {
+      Block::Paragraph(spans) => flatten(spans),
+      Block::Quote(spans) => format!("> {}", flatten(spans)),
+      Block::Code(code) => code.clone(),
+    })
+    .collect::<Vec<_>>()
+    .join("\n\n")
+}
+
+#[derive(Default)]
+struct Parser {
+  blocks: Vec<Block>,
+  spans: Vec<Span>,
+  italic_depth: usize,
+  link: Option<String>,
+  // `Some` while inside `<pre>`.
+  code: Option<String>,
+}
+
+impl Parser {
+  fn handle_tag(&mut self, tag: &str) {
+    let (is_closing, tag) = match tag.strip_prefix('/') {
+      Some(tag) => (true, tag),
+      None => (false, tag),
+    };
+    let name = tag.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or_default().to_ascii_lowercase();
+    match (name.as_str(), is_closing) {
+      ("p", false) => self.finish_paragraph(),
+      ("i" | "em", false) => self.italic_depth += 1,
+      ("i" | "em", true) => self.italic_depth = self.italic_depth.saturating_sub(1),
+      ("a", false) => self.link = attribute(tag, "href"),
+      ("a", true) => self.link = None,
+      ("pre", false) => {
+        self.finish_paragraph();
+        self.code = Some(String::new());
+      }
+      ("pre", true) => {
+        if let Some(code) = self.code.take() {
+          self.blocks.push(Block::Code(code.trim_end().to_string()));
+        }
+      }
+      _ => {}
+    }
+  }
+
+  fn push_text(&mut self, raw: &str) {
+    if raw.is_empty() {
+      return;
+    }
+    let text = decode_entities(raw);
+    if let Some(code) = &mut self.code {
+      code.push_str(&text);
+      return;
+    }
+    // Outside of code blocks line breaks carry no meaning, as in a browser.
+    let text = text.replace(['\n', '\r'], " ");
+    let italic = self.italic_depth > 0;
+    match self.spans.last_mut() {
+      Some(last) if last.italic == italic && last.link == self.link => last.text.push_str(&text),
+      _ => self.spans.push(Span { text, italic, link: self.link.clone() }),
+    }
+  }
+
+  fn finish_paragraph(&mut self) {
+    let mut spans = std::mem::take(&mut self.spans);
+    if let Some(first) = spans.first_mut() {
+      first.text = first.text.trim_start().to_string();
+    }
+    if let Some(last) = spans.last_mut() {
+      last.text = last.text.trim_end().to_string();
+    }
+    let mut is_quote = false;
+    if let Some(first) = spans.first_mut()
+      && let Some(quoted) = first.text.strip_prefix('>')
+    {
+      first.text = quoted.trim_start_matches('>').trim_start().to_string();
+      is_quote = true;
+    }
+    spans.retain(|span| !span.text.is_empty());
+    if !spans.is_empty() {
+      self.blocks.push(if is_quote { Block::Quote(spans) } else { Block::Paragraph(spans) });
+    }
+  }
+
+  fn finish(mut self) -> Vec<Block> {
+    if let Some(code) = self.code.take() {
+      self.blocks.push(Block::Code(code.trim_end().to_string()));
+    }
+    self.finish_paragraph();
+    self.blocks
+  }
+}
+
+// Reads a double-quoted attribute from the inside of a tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+  let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+  let len = tag[start..].find('"')?;
+  Some(decode_entities(&tag[start..start + len]))
+}
+
+/// Replaces the named and numeric character references HN uses with their characters.
+fn decode_entities(text: &str) -> String {
+  let mut decoded = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('&') {
+    decoded.push_str(&rest[..start]);
+    rest = &rest[start..];
+    let entity =
+      rest[1..].find(';').filter(|&len| len <= 8).and_then(|len| Some((decode_entity(&rest[1..=len])?, len)));
+    match entity {
+      Some((c, len)) => {
+        decoded.push(c);
+        rest = &rest[len + 2..];
+      }
+      None => {
+        decoded.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+  decoded.push_str(rest);
+  decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+  match name {
+    "amp" => Some('&'),
+    "lt" => Some('<'),
+    "gt" => Some('>'),
+    "quot" => Some('"'),
+    "apos" => Some('\''),
+    "nbsp" => Some('\u{a0}'),
+    _ => {
+      let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => name.strip_prefix('#')?.parse().ok()?,
+      };
+      char::from_u32(code)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn text(text: &str) -> Span {
+    Span { text: text.to_string(), ..Span::default() }
+  }
+
+  #[test]
+  fn paragraphs_are_split_on_unclosed_p_tags() {
+    assert_eq!(
+      parse("First line\nwraps.<p>Second.<p><p>Third."),
+      [
+        Block::Paragraph(vec![text("First line wraps.")]),
+        Block::Paragraph(vec![text("Second.")]),
+        Block::Paragraph(vec![text("Third.")]),
+      ]
+    );
+  }
+
+  #[test]
+  fn quotes_drop_their_leading_markers() {
+    assert_eq!(
+      parse("&gt; Quoted text<p>>> nested<p>Reply"),
+      [
+        Block::Quote(vec![text("Quoted text")]),
+        Block::Quote(vec![text("nested")]),
+        Block::Paragraph(vec![text("Reply")]),
+      ]
+    );
+  }
+
+  #[test]
+  fn nested_italics_and_links_style_their_spans() {
+    let link = Some("https://example.com/?a=1&b=2".to_string());
+    assert_eq!(
+      parse(
+        r#"<i>one <em>two</em> three</i> <a href="https://example.com/?a=1&amp;b=2" rel="nofollow"><i>four</i></a>"#
+      ),
+      [Block::Paragraph(vec![
+        Span { text: "one two three".to_string(), italic: true, link: None },
+        text(" "),
+        Span { text: "four".to_string(), italic: true, link },
+      ])]
+    );
+  }
+
+  #[test]
+  fn code_blocks_keep_their_whitespace() {
+    assert_eq!(
+      parse("Try:<p><pre><code>  fn main() {\n    x &lt; y;\n  }\n</code></pre>Done."),
+      [
+        Block::Paragraph(vec![text("Try:")]),
+        Block::Code("  fn main() {\n    x < y;\n  }".to_string()),
+        Block::Paragraph(vec![text("Done.")]),
+      ]
+    );
+  }
+
+  #[test]
+  fn malformed_markup_degrades_to_text() {
+    assert_eq!(parse("<b>bold</b> <unknown attr>kept"), [Block::Paragraph(vec![text("bold kept")])]);
+    assert_eq!(parse("a < b"), [Block::Paragraph(vec![text("a < b")])]);
+    assert_eq!(parse("</i></a>unbalanced<a>"), [Block::Paragraph(vec![text("unbalanced")])]);
+    assert_eq!(parse("<pre>never closed\n"), [Block::Code("never closed".to_string())]);
+    assert_eq!(parse(r#"<a href="unterminated>x</a>"#), [Block::Paragraph(vec![text("x")])]);
+    assert_eq!(parse(""), []);
+  }
+
+  #[test]
+  fn entities_are_decoded() {
+    assert_eq!(decode_entities("&amp;&lt;&gt;&quot;&apos;&nbsp;"), "&<>\"'\u{a0}");
+    assert_eq!(decode_entities("it&#x27;s &#X2F; &#8212; done"), "it's / \u{2014} done");
+  }
+
+  #[test]
+  fn unknown_or_broken_entities_are_kept() {
+    assert_eq!(decode_entities("AT&T &bogus; &#xZZ; &#1114112; &amp"), "AT&T &bogus; &#xZZ; &#1114112; &amp");
+    assert_eq!(decode_entities("&&amp;;"), "&&;");
+  }
+
+  #[test]
+  fn plain_text_separates_blocks() {
+    assert_eq!(to_plain_text("One<p>&gt; Two<p><pre><code>three</code></pre>"), "One\n\n> Two\n\nthree");
+  }
+}
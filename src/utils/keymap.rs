@@ -0,0 +1,482 @@
+//! Maps key chords to named actions: the built-in defaults, overridden per action
+//! by the user's `keymap.toml` in the configuration directory, e.g.
+//!
+//! ```toml
+//! [bindings]
+//! focus_next = ["j", "down", "ctrl+n"]
+//! focus_previous = ["k", "up", "ctrl+p"]
+//! toggle_theme = "shift+t"
+//! refresh = []            # unbound
+//! ```
+//!
+//! Listing an action replaces all of its default chords, and takes its chords away
+//! from the defaults of other actions. Unknown actions, chords that cannot be parsed
+//! and chords listed twice within overlapping scopes are reported at startup and
+//! skipped.
+
+use crate::utils::keys::{KeyAction, Scope};
+use crate::utils::paths;
+use freya::prelude::{Code, Key, Modifiers};
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+const KEYMAP_FILE_NAME: &str = "keymap.toml";
+
+// Named keys as written in the keymap file, with their aliases and display names.
+const NAMED_KEYS: [(&str, &[&str], &str); 14] = [
+  ("enter", &["return"], "Enter"),
+  ("escape", &["esc"], "Esc"),
+  ("backspace", &[], "Backspace"),
+  ("tab", &[], "Tab"),
+  ("space", &[], "Space"),
+  ("up", &["arrowup"], "↑"),
+  ("down", &["arrowdown"], "↓"),
+  ("left", &["arrowleft"], "←"),
+  ("right", &["arrowright"], "→"),
+  ("pageup", &[], "PageUp"),
+  ("pagedown", &[], "PageDown"),
+  ("home", &[], "Home"),
+  ("end", &[], "End"),
+  ("delete", &["del"], "Delete"),
+];
+
+/// A key together with the modifiers held while pressing it, e.g. `ctrl+k`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+  // A lowercase character or one of the names in `NAMED_KEYS`.
+  key: String,
+  ctrl: bool,
+  alt: bool,
+  shift: bool,
+  meta: bool,
+}
+
+impl KeyChord {
+  /// The chord of a key press, or `None` for keys that cannot be bound.
+  pub fn from_event(key: &Key, code: Code, modifiers: Modifiers) -> Option<Self> {
+    let name = if code == Code::Space {
+      "space".to_string()
+    } else {
+      match key {
+        Key::Character(c) => c.to_lowercase(),
+        Key::Enter => "enter".to_string(),
+        Key::Escape => "escape".to_string(),
+        Key::Backspace => "backspace".to_string(),
+        Key::Tab => "tab".to_string(),
+        Key::ArrowUp => "up".to_string(),
+        Key::ArrowDown => "down".to_string(),
+        Key::ArrowLeft => "left".to_string(),
+        Key::ArrowRight => "right".to_string(),
+        Key::PageUp => "pageup".to_string(),
+        Key::PageDown => "pagedown".to_string(),
+        Key::Home => "home".to_string(),
+        Key::End => "end".to_string(),
+        Key::Delete => "delete".to_string(),
+        _ => return None,
+      }
+    };
+    Some(Self {
+      ctrl: modifiers.contains(Modifiers::CONTROL),
+      alt: modifiers.contains(Modifiers::ALT),
+      shift: modifiers.contains(Modifiers::SHIFT) && !is_symbol(&name),
+      key: name,
+      meta: modifiers.contains(Modifiers::META),
+    })
+  }
+}
+
+// Shift only changes which character a digit or symbol key produces (`/` or `?`), so
+// it is not part of their chord.
+fn is_symbol(key: &str) -> bool {
+  let mut chars = key.chars();
+  matches!((chars.next(), chars.next()), (Some(c), None) if !c.is_alphabetic())
+}
+
+impl FromStr for KeyChord {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut chord = Self { key: String::new(), ctrl: false, alt: false, shift: false, meta: false };
+    let parts: Vec<&str> = s.split('+').map(str::trim).collect();
+    // A trailing empty part means the key itself is `+`, as in `ctrl++`.
+    let (key, modifiers) = match parts.as_slice() {
+      [modifiers @ .., "", ""] => ("+", modifiers),
+      [modifiers @ .., key] => (*key, modifiers),
+      [] => unreachable!("split always yields at least one part"),
+    };
+    for modifier in modifiers {
+      match modifier.to_lowercase().as_str() {
+        "ctrl" | "control" => chord.ctrl = true,
+        "alt" | "option" => chord.alt = true,
+        "shift" => chord.shift = true,
+        "meta" | "super" | "cmd" => chord.meta = true,
+        other => return Err(format!("unknown modifier \"{other}\"")),
+      }
+    }
+    let mut chars = key.chars();
+    chord.key = match (chars.next(), chars.next()) {
+      (None, _) => return Err("missing key".to_string()),
+      // An uppercase letter is the same as shift and the lowercase letter.
+      (Some(c), None) if c.is_uppercase() => {
+        chord.shift = true;
+        c.to_lowercase().to_string()
+      }
+      (Some(c), None) => c.to_string(),
+      _ => {
+        let lower = key.to_lowercase();
+        NAMED_KEYS
+          .iter()
+          .find(|(name, aliases, _)| *name == lower || aliases.contains(&lower.as_str()))
+          .map(|(name, _, _)| name.to_string())
+          .ok_or_else(|| format!("unknown key \"{key}\""))?
+      }
+    };
+    if chord.shift && is_symbol(&chord.key) {
+      return Err(format!("shift cannot be combined with \"{}\"; use the character it types instead", chord.key));
+    }
+    Ok(chord)
+  }
+}
+
+impl fmt::Display for KeyChord {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (held, name) in [(self.ctrl, "Ctrl"), (self.alt, "Alt"), (self.shift, "Shift"), (self.meta, "Meta")] {
+      if held {
+        write!(f, "{name}+")?;
+      }
+    }
+    match NAMED_KEYS.iter().find(|(name, _, _)| *name == self.key) {
+      Some((_, _, display)) => write!(f, "{display}"),
+      None => write!(f, "{}", self.key),
+    }
+  }
+}
+
+/// A problem found in the keymap file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeymapIssue {
+  Unreadable(String),
+  UnknownAction(String),
+  InvalidChord {
+    action: String,
+    chord: String,
+    reason: String,
+  },
+  /// `chord` was requested for `dropped` but is already bound to `kept`.
+  Conflict {
+    chord: String,
+    kept: String,
+    dropped: String,
+  },
+}
+
+impl fmt::Display for KeymapIssue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      KeymapIssue::Unreadable(reason) => write!(f, "keymap file could not be read: {reason}"),
+      KeymapIssue::UnknownAction(action) => write!(f, "unknown action \"{action}\""),
+      KeymapIssue::InvalidChord { action, chord, reason } => {
+        write!(f, "invalid key \"{chord}\" for \"{action}\": {reason}")
+      }
+      KeymapIssue::Conflict { chord, kept, dropped } => {
+        write!(f, "{chord} is bound to both \"{kept}\" and \"{dropped}\"; keeping \"{kept}\"")
+      }
+    }
+  }
+}
+
+// The keymap file as written by the user.
+#[derive(Deserialize)]
+struct KeymapFile {
+  #[serde(default)]
+  bindings: BTreeMap<String, Chords>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Chords {
+  One(String),
+  Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+  bindings: Vec<(KeyChord, KeyAction)>,
+  issues: Vec<KeymapIssue>,
+}
+
+impl Default for Keymap {
+  fn default() -> Self {
+    Self::resolve(
+      Vec::new(),
+      KeyAction::ALL.iter().map(|action| (*action, parse_defaults(*action))).collect(),
+      Vec::new(),
+    )
+  }
+}
+
+impl Keymap {
+  /// Loads the keymap file from the configuration directory, falling back to the
+  /// defaults when there is none. Every issue found is logged.
+  pub fn load() -> Self {
+    let Some(path) = paths::config_dir().map(|dir| dir.join(KEYMAP_FILE_NAME)) else {
+      return Self::default();
+    };
+    let keymap = Self::from_file(&path);
+    for issue in &keymap.issues {
+      warn!("{}: {}", path.display(), issue);
+    }
+    keymap
+  }
+
+  fn from_file(path: &Path) -> Self {
+    match std::fs::read_to_string(path) {
+      Ok(contents) => {
+        info!("Loading key bindings from {}", path.display());
+        Self::from_toml(&contents)
+      }
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+      Err(e) => Self::resolve_defaults_with(vec![KeymapIssue::Unreadable(e.to_string())]),
+    }
+  }
+
+  /// Builds a keymap from the contents of a keymap file.
+  pub fn from_toml(contents: &str) -> Self {
+    let file: KeymapFile = match toml::from_str(contents) {
+      Ok(file) => file,
+      Err(e) => return Self::resolve_defaults_with(vec![KeymapIssue::Unreadable(e.message().to_string())]),
+    };
+    let mut issues = Vec::new();
+    let mut listed: Vec<(KeyAction, Vec<KeyChord>)> = Vec::new();
+    for (name, requested) in file.bindings {
+      let Some(action) = KeyAction::from_name(&name) else {
+        issues.push(KeymapIssue::UnknownAction(name));
+        continue;
+      };
+      let requested = match requested {
+        Chords::One(chord) => vec![chord],
+        Chords::Many(chords) => chords,
+      };
+      let parsed = requested
+        .into_iter()
+        .filter_map(|chord| match chord.parse() {
+          Ok(parsed) => Some(parsed),
+          Err(reason) => {
+            issues.push(KeymapIssue::InvalidChord { action: name.clone(), chord, reason });
+            None
+          }
+        })
+        .collect();
+      listed.push((action, parsed));
+    }
+    // Resolved in the order of `KeyAction::ALL`, like the defaults.
+    listed.sort_by_key(|(action, _)| KeyAction::ALL.iter().position(|a| a == action));
+    let defaults = KeyAction::ALL
+      .iter()
+      .filter(|action| !listed.iter().any(|(a, _)| a == *action))
+      .map(|action| (*action, parse_defaults(*action)))
+      .collect();
+    Self::resolve(listed, defaults, issues)
+  }
+
+  fn resolve_defaults_with(issues: Vec<KeymapIssue>) -> Self {
+    Self { issues, ..Self::default() }
+  }
+
+  // Binds the chords listed in the keymap file first, skipping (and reporting) those an
+  // earlier listed action in an overlapping scope already uses. The defaults of the
+  // other actions come after, so a chord the file reassigns is dropped from its default
+  // action without a report.
+  fn resolve(
+    listed: Vec<(KeyAction, Vec<KeyChord>)>,
+    defaults: Vec<(KeyAction, Vec<KeyChord>)>,
+    mut issues: Vec<KeymapIssue>,
+  ) -> Self {
+    let mut bindings: Vec<(KeyChord, KeyAction)> = Vec::new();
+    let listed_count = listed.len();
+    for (index, (action, action_chords)) in listed.into_iter().chain(defaults).enumerate() {
+      for chord in action_chords {
+        let taken = bindings.iter().find(|(bound, other)| *bound == chord && other.scope().overlaps(action.scope()));
+        match taken {
+          Some((_, other)) if *other == action => {}
+          Some((_, other)) if index < listed_count => issues.push(KeymapIssue::Conflict {
+            chord: chord.to_string(),
+            kept: other.name().into_owned(),
+            dropped: action.name().into_owned(),
+          }),
+          Some(_) => {}
+          None => bindings.push((chord, action)),
+        }
+      }
+    }
+    Self { bindings, issues }
+  }
+
+  /// The action bound to `chord` that applies in `scope`.
+  pub fn action(&self, chord: &KeyChord, scope: Scope) -> Option<KeyAction> {
+    self
+      .bindings
+      .iter()
+      .find(|(bound, action)| bound == chord && action.scope().overlaps(scope))
+      .map(|(_, action)| *action)
+  }
+
+  /// The chords bound to `action`, in the order they were listed.
+  pub fn chords_for(&self, action: KeyAction) -> impl Iterator<Item = &KeyChord> {
+    self.bindings.iter().filter(move |(_, bound)| *bound == action).map(|(chord, _)| chord)
+  }
+
+  /// Problems found while loading the keymap file.
+  pub fn issues(&self) -> &[KeymapIssue] {
+    &self.issues
+  }
+}
+
+fn parse_defaults(action: KeyAction) -> Vec<KeyChord> {
+  action.default_chords().into_iter().map(|chord| chord.parse().expect("default key chords are valid")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chord(s: &str) -> KeyChord {
+    s.parse().unwrap()
+  }
+
+  fn chords(keymap: &Keymap, action: KeyAction) -> Vec<String> {
+    keymap.chords_for(action).map(ToString::to_string).collect()
+  }
+
+  #[test]
+  fn chords_parse_modifiers_and_aliases() {
+    let ctrl_k = KeyChord { key: "k".to_string(), ctrl: true, alt: false, shift: false, meta: false };
+    assert_eq!(chord("ctrl+k"), ctrl_k);
+    assert_eq!(chord("Control + K").to_string(), "Ctrl+Shift+k");
+    assert_eq!(chord("esc"), chord("escape"));
+    assert_eq!(chord("Return"), chord("enter"));
+    assert_eq!(chord("ArrowUp").to_string(), "↑");
+    assert_eq!(chord("cmd+option+del").to_string(), "Alt+Meta+Delete");
+  }
+
+  #[test]
+  fn uppercase_letters_mean_shift() {
+    assert_eq!(chord("N"), chord("shift+n"));
+    assert_eq!(chord("shift+N"), chord("shift+n"));
+    assert_ne!(chord("N"), chord("n"));
+  }
+
+  #[test]
+  fn plus_can_be_bound_as_a_key() {
+    assert_eq!(chord("ctrl++").to_string(), "Ctrl++");
+    assert_eq!(chord("+").to_string(), "+");
+  }
+
+  #[test]
+  fn shift_is_rejected_for_digits_and_symbols() {
+    for s in ["shift+/", "shift+1", "ctrl+shift+["] {
+      assert!(s.parse::<KeyChord>().unwrap_err().contains("shift"), "{s}");
+    }
+    assert_eq!(chord("?").to_string(), "?");
+  }
+
+  #[test]
+  fn malformed_chords_are_rejected() {
+    assert_eq!("hyper+k".parse::<KeyChord>(), Err("unknown modifier \"hyper\"".to_string()));
+    assert_eq!("ctrl+".parse::<KeyChord>(), Err("missing key".to_string()));
+    assert_eq!("ctrl+f13".parse::<KeyChord>(), Err("unknown key \"f13\"".to_string()));
+  }
+
+  #[test]
+  fn actions_are_named_in_the_keymap_file() {
+    for action in KeyAction::ALL {
+      assert_eq!(KeyAction::from_name(&action.name()), Some(action));
+    }
+    assert_eq!(KeyAction::SelectTab(0).name(), "select_tab_1");
+    assert_eq!(KeyAction::from_name("select_tab_0"), None);
+    assert_eq!(KeyAction::from_name("select_tab_7"), None);
+    assert_eq!(KeyAction::from_name("focus"), None);
+  }
+
+  #[test]
+  fn the_defaults_have_no_issues() {
+    let keymap = Keymap::default();
+    assert_eq!(keymap.issues(), []);
+    assert_eq!(keymap.action(&chord("j"), Scope::List), Some(KeyAction::FocusNext));
+    assert_eq!(keymap.action(&chord("enter"), Scope::List), Some(KeyAction::OpenStory));
+    assert_eq!(keymap.action(&chord("enter"), Scope::Detail), Some(KeyAction::ToggleComment));
+    assert_eq!(keymap.action(&chord("n"), Scope::List), None);
+  }
+
+  #[test]
+  fn listed_actions_replace_their_defaults() {
+    let keymap = Keymap::from_toml("[bindings]\nfocus_next = [\"ctrl+n\"]\ntoggle_theme = \"shift+t\"\n");
+    assert_eq!(keymap.issues(), []);
+    assert_eq!(chords(&keymap, KeyAction::FocusNext), ["Ctrl+n"]);
+    assert_eq!(chords(&keymap, KeyAction::ToggleTheme), ["Shift+t"]);
+    assert_eq!(keymap.action(&chord("j"), Scope::List), None);
+  }
+
+  #[test]
+  fn listed_chords_win_over_other_actions_defaults() {
+    let keymap = Keymap::from_toml("[bindings]\nnext_tab = \"j\"\n");
+    assert_eq!(keymap.issues(), []);
+    assert_eq!(keymap.action(&chord("j"), Scope::List), Some(KeyAction::NextTab));
+    assert_eq!(chords(&keymap, KeyAction::FocusNext), ["↓"]);
+  }
+
+  #[test]
+  fn an_empty_list_unbinds_an_action() {
+    let keymap = Keymap::from_toml("[bindings]\nrefresh = []\n");
+    assert_eq!(keymap.issues(), []);
+    assert_eq!(chords(&keymap, KeyAction::Refresh), Vec::<String>::new());
+    assert_eq!(keymap.action(&chord("r"), Scope::Global), None);
+  }
+
+  #[test]
+  fn unknown_actions_and_invalid_chords_are_reported() {
+    let keymap = Keymap::from_toml("[bindings]\njump = \"x\"\nrefresh = [\"shift+1\", \"f5\", \"ctrl+r\"]\n");
+    assert_eq!(
+      keymap.issues(),
+      [
+        KeymapIssue::UnknownAction("jump".to_string()),
+        KeymapIssue::InvalidChord {
+          action: "refresh".to_string(),
+          chord: "shift+1".to_string(),
+          reason: "shift cannot be combined with \"1\"; use the character it types instead".to_string(),
+        },
+        KeymapIssue::InvalidChord {
+          action: "refresh".to_string(),
+          chord: "f5".to_string(),
+          reason: "unknown key \"f5\"".to_string(),
+        },
+      ]
+    );
+    assert_eq!(chords(&keymap, KeyAction::Refresh), ["Ctrl+r"]);
+  }
+
+  #[test]
+  fn conflicting_listed_chords_keep_the_first_action() {
+    let keymap =
+      Keymap::from_toml("[bindings]\nrefresh = \"x\"\nback = \"x\"\nopen_story = \"o\"\ntoggle_comment = \"o\"\n");
+    assert_eq!(
+      keymap.issues(),
+      [KeymapIssue::Conflict { chord: "x".to_string(), kept: "back".to_string(), dropped: "refresh".to_string() }]
+    );
+    assert_eq!(keymap.action(&chord("x"), Scope::List), Some(KeyAction::Back));
+    // List and detail actions never apply at the same time, so they may share a chord.
+    assert_eq!(keymap.action(&chord("o"), Scope::List), Some(KeyAction::OpenStory));
+    assert_eq!(keymap.action(&chord("o"), Scope::Detail), Some(KeyAction::ToggleComment));
+  }
+
+  #[test]
+  fn unparsable_files_fall_back_to_the_defaults() {
+    let keymap = Keymap::from_toml("[bindings\n");
+    assert!(matches!(keymap.issues(), [KeymapIssue::Unreadable(_)]));
+    assert_eq!(keymap.bindings, Keymap::default().bindings);
+  }
+}
@@ -0,0 +1,291 @@
+//! Keyboard shortcuts: the named actions key presses trigger, and the hook views use
+//! to resolve key presses through the user's keymap.
+
+use crate::utils::keymap::{KeyChord, Keymap};
+use accesskit::Role;
+use freya::prelude::*;
+use std::borrow::Cow;
+
+// The keys selecting the story list tabs by default, by tab index.
+const TAB_KEYS: [&str; 6] = ["1", "2", "3", "4", "5", "6"];
+
+/// Where an action applies. Two actions may share a chord only if they never
+/// apply at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+  /// Everywhere in the app.
+  Global,
+  /// The story list.
+  List,
+  /// A story's detail view or a user profile.
+  Detail,
+}
+
+impl Scope {
+  pub fn overlaps(self, other: Scope) -> bool {
+    self == other || self == Scope::Global || other == Scope::Global
+  }
+}
+
+/// A named app action that can be bound to key chords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+  /// Moves the focus cursor to the next story or comment.
+  FocusNext,
+  /// Moves the focus cursor to the previous story or comment.
+  FocusPrevious,
+  OpenStory,
+  /// Expands or collapses the focused comment.
+  ToggleComment,
+  /// Opens the story after the current one in the list it was opened from.
+  NextStory,
+  PreviousStory,
+  /// Moves the focus cursor to the next comment that is new since the last visit.
+  NextNewComment,
+  /// Goes back in the navigation history.
+  Back,
+  Forward,
+  /// Closes the tab of the story shown.
+  CloseTab,
+  NextTab,
+  PreviousTab,
+  /// Switches to the story list tab at this index.
+  SelectTab(usize),
+  Refresh,
+  ToggleTheme,
+  PageDown,
+  PageUp,
+  /// Opens the command palette.
+  CommandPalette,
+}
+
+impl KeyAction {
+  /// Every action, in the order conflicting bindings are resolved in.
+  pub const ALL: [KeyAction; 23] = [
+    KeyAction::FocusNext,
+    KeyAction::FocusPrevious,
+    KeyAction::OpenStory,
+    KeyAction::ToggleComment,
+    KeyAction::NextStory,
+    KeyAction::PreviousStory,
+    KeyAction::NextNewComment,
+    KeyAction::Back,
+    KeyAction::Forward,
+    KeyAction::CloseTab,
+    KeyAction::NextTab,
+    KeyAction::PreviousTab,
+    KeyAction::SelectTab(0),
+    KeyAction::SelectTab(1),
+    KeyAction::SelectTab(2),
+    KeyAction::SelectTab(3),
+    KeyAction::SelectTab(4),
+    KeyAction::SelectTab(5),
+    KeyAction::Refresh,
+    KeyAction::ToggleTheme,
+    KeyAction::PageDown,
+    KeyAction::PageUp,
+    KeyAction::CommandPalette,
+  ];
+
+  /// The name used for this action in the keymap file.
+  pub fn name(&self) -> Cow<'static, str> {
+    Cow::Borrowed(match self {
+      KeyAction::FocusNext => "focus_next",
+      KeyAction::FocusPrevious => "focus_previous",
+      KeyAction::OpenStory => "open_story",
+      KeyAction::ToggleComment => "toggle_comment",
+      KeyAction::NextStory => "next_story",
+      KeyAction::PreviousStory => "previous_story",
+      KeyAction::NextNewComment => "next_new_comment",
+      KeyAction::Back => "back",
+      KeyAction::Forward => "forward",
+      KeyAction::CloseTab => "close_tab",
+      KeyAction::NextTab => "next_tab",
+      KeyAction::PreviousTab => "previous_tab",
+      KeyAction::SelectTab(index) => return Cow::Owned(format!("select_tab_{}", index + 1)),
+      KeyAction::Refresh => "refresh",
+      KeyAction::ToggleTheme => "toggle_theme",
+      KeyAction::PageDown => "page_down",
+      KeyAction::PageUp => "page_up",
+      KeyAction::CommandPalette => "command_palette",
+    })
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    if let Some(number) = name.strip_prefix("select_tab_") {
+      let action = KeyAction::SelectTab(number.parse::<usize>().ok()?.checked_sub(1)?);
+      return Self::ALL.contains(&action).then_some(action);
+    }
+    Self::ALL.into_iter().find(|action| !matches!(action, KeyAction::SelectTab(_)) && action.name() == name)
+  }
+
+  pub fn scope(&self) -> Scope {
+    match self {
+      KeyAction::OpenStory => Scope::List,
+      KeyAction::ToggleComment | KeyAction::NextStory | KeyAction::PreviousStory | KeyAction::NextNewComment => {
+        Scope::Detail
+      }
+      _ => Scope::Global,
+    }
+  }
+
+  /// The chords bound to this action unless the keymap file says otherwise.
+  pub fn default_chords(&self) -> Vec<&'static str> {
+    match self {
+      KeyAction::FocusNext => vec!["j", "down"],
+      KeyAction::FocusPrevious => vec!["k", "up"],
+      KeyAction::OpenStory | KeyAction::ToggleComment => vec!["enter"],
+      KeyAction::NextStory => vec!["n"],
+      KeyAction::PreviousStory => vec!["p"],
+      KeyAction::NextNewComment => vec!["shift+n"],
+      KeyAction::Back => vec!["escape", "backspace", "alt+left"],
+      KeyAction::Forward => vec!["alt+right"],
+      KeyAction::CloseTab => vec!["ctrl+w"],
+      KeyAction::NextTab => vec!["]"],
+      KeyAction::PreviousTab => vec!["["],
+      KeyAction::SelectTab(index) => TAB_KEYS.get(*index).into_iter().copied().collect(),
+      KeyAction::Refresh => vec!["r"],
+      KeyAction::ToggleTheme => vec!["t"],
+      KeyAction::PageDown => vec!["space", "pagedown"],
+      KeyAction::PageUp => vec!["shift+space", "pageup"],
+      KeyAction::CommandPalette => vec!["ctrl+k"],
+    }
+  }
+}
+
+/// Resolves key presses into actions for views that listen to `onglobalkeydown`.
+#[derive(Clone, Copy)]
+pub struct KeyActions {
+  typing: Memo<bool>,
+  keymap: Signal<Keymap>,
+}
+
+impl KeyActions {
+  /// The action a key press triggers in `scope`, or `None` while a text input has
+  /// focus so that typing into it does not trigger shortcuts.
+  pub fn action(&self, e: &KeyboardEvent, scope: Scope) -> Option<KeyAction> {
+    if *self.typing.peek() {
+      return None;
+    }
+    let chord = KeyChord::from_event(&e.key, e.code, e.modifiers)?;
+    self.keymap.peek().action(&chord, scope)
+  }
+}
+
+/// Requires the `Keymap` signal to be provided as context.
+pub fn use_key_actions() -> KeyActions {
+  let focused_node = use_focus().focused_node();
+  let typing = use_memo(move || focused_node.read().role() == Role::TextInput);
+  let keymap = use_context::<Signal<Keymap>>();
+  KeyActions { typing, keymap }
+}
+
+/// Scrolls a scroll view by whole pages of its visible height; negative pages scroll up.
+pub fn scroll_by_pages(scroll_controller: ScrollController, pages: i32) {
+  let layout = scroll_controller.layout().peek().clone();
+  let page = layout.area.height() as i32;
+  let max_offset = (layout.inner.height - layout.area.height()).max(0.0) as i32;
+  let mut y = scroll_controller.y();
+  let target = (*y.peek() - pages * page).clamp(-max_offset, 0);
+  y.set(target);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dioxus::dioxus_core::{ScopeId, VNode, VirtualDom};
+  use freya::core::custom_attributes::NodeReferenceLayout;
+
+  // The action a key press triggers in `scope` with the default keymap.
+  fn action(key: Key, code: Code, modifiers: Modifiers, scope: Scope) -> Option<KeyAction> {
+    Keymap::default().action(&KeyChord::from_event(&key, code, modifiers)?, scope)
+  }
+
+  fn character(c: &str, scope: Scope) -> Option<KeyAction> {
+    action(Key::Character(c.to_string()), Code::Unidentified, Modifiers::empty(), scope)
+  }
+
+  fn named(key: Key, code: Code, scope: Scope) -> Option<KeyAction> {
+    action(key, code, Modifiers::empty(), scope)
+  }
+
+  #[test]
+  fn list_and_detail_scopes_only_overlap_with_themselves_and_global() {
+    assert!(Scope::List.overlaps(Scope::List));
+    assert!(!Scope::List.overlaps(Scope::Detail));
+    assert!(!Scope::Detail.overlaps(Scope::List));
+    for scope in [Scope::Global, Scope::List, Scope::Detail] {
+      assert!(Scope::Global.overlaps(scope));
+      assert!(scope.overlaps(Scope::Global));
+    }
+  }
+
+  #[test]
+  fn vim_keys_and_arrows_move_the_focus() {
+    for scope in [Scope::List, Scope::Detail] {
+      assert_eq!(character("j", scope), Some(KeyAction::FocusNext));
+      assert_eq!(character("k", scope), Some(KeyAction::FocusPrevious));
+      assert_eq!(named(Key::ArrowDown, Code::ArrowDown, scope), Some(KeyAction::FocusNext));
+      assert_eq!(named(Key::ArrowUp, Code::ArrowUp, scope), Some(KeyAction::FocusPrevious));
+    }
+  }
+
+  #[test]
+  fn enter_opens_stories_and_toggles_comments() {
+    assert_eq!(named(Key::Enter, Code::Enter, Scope::List), Some(KeyAction::OpenStory));
+    assert_eq!(named(Key::Enter, Code::Enter, Scope::Detail), Some(KeyAction::ToggleComment));
+    assert_eq!(named(Key::Escape, Code::Escape, Scope::Detail), Some(KeyAction::Back));
+    assert_eq!(named(Key::Backspace, Code::Backspace, Scope::Detail), Some(KeyAction::Back));
+  }
+
+  #[test]
+  fn number_keys_select_tabs_from_one() {
+    assert_eq!(character("1", Scope::Global), Some(KeyAction::SelectTab(0)));
+    assert_eq!(character("6", Scope::Global), Some(KeyAction::SelectTab(5)));
+    assert_eq!(character("0", Scope::Global), None);
+    assert_eq!(character("r", Scope::Global), Some(KeyAction::Refresh));
+    assert_eq!(character("x", Scope::Global), None);
+  }
+
+  #[test]
+  fn space_pages_down_and_shift_space_up() {
+    let space = Key::Character(" ".to_string());
+    assert_eq!(named(space.clone(), Code::Space, Scope::List), Some(KeyAction::PageDown));
+    assert_eq!(action(space, Code::Space, Modifiers::SHIFT, Scope::List), Some(KeyAction::PageUp));
+    assert_eq!(named(Key::PageDown, Code::PageDown, Scope::Detail), Some(KeyAction::PageDown));
+    assert_eq!(named(Key::PageUp, Code::PageUp, Scope::Detail), Some(KeyAction::PageUp));
+  }
+
+  #[test]
+  fn keys_held_with_ctrl_alt_or_meta_are_not_bound() {
+    let j = Key::Character("j".to_string());
+    for modifiers in [Modifiers::CONTROL, Modifiers::ALT, Modifiers::META] {
+      assert_eq!(action(j.clone(), Code::KeyJ, modifiers, Scope::List), None);
+    }
+  }
+
+  #[test]
+  fn scrolling_by_pages_stops_at_the_top_and_bottom() {
+    let dom = VirtualDom::new(VNode::empty);
+    dom.in_runtime(|| {
+      ScopeId::ROOT.in_runtime(|| {
+        // A 100 pixel high view of 250 pixels of content, so at most 150 can be scrolled.
+        let scroll_controller = ScrollController::new(0, 0, vec![]);
+        scroll_controller.layout().set(NodeReferenceLayout {
+          area: Area::new((0.0, 0.0).into(), (100.0, 100.0).into()),
+          inner: (100.0, 250.0).into(),
+        });
+        let y = scroll_controller.y();
+
+        scroll_by_pages(scroll_controller, 1);
+        assert_eq!(*y.peek(), -100);
+        scroll_by_pages(scroll_controller, 1);
+        assert_eq!(*y.peek(), -150);
+        scroll_by_pages(scroll_controller, -1);
+        assert_eq!(*y.peek(), -50);
+        scroll_by_pages(scroll_controller, -1);
+        assert_eq!(*y.peek(), 0);
+      })
+    });
+  }
+}
@@ -0,0 +1,153 @@
+//! Story lists kept in memory per tab, so that switching back to a tab shows it
+//! as it was left instead of fetching it again.
+
+use crate::models::{FailedStory, Story};
+use crate::utils::api::StoryListType;
+use std::collections::HashMap;
+use std::env;
+use std::time::{Duration, Instant};
+
+// How long a list's ids are used before switching to the tab fetches them again.
+const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// A tab's list as it was last shown.
+#[derive(Clone)]
+pub struct CachedList {
+  pub ids: Vec<u32>,
+  pub stories: Vec<Story>,
+  pub failed: Vec<FailedStory>,
+  /// How many of `ids` were requested to be loaded.
+  pub loaded_count: usize,
+  /// Index into `stories` of the story the focus cursor was on.
+  pub focused: Option<usize>,
+  fetched_at: Instant,
+}
+
+pub struct ListCache {
+  lists: HashMap<StoryListType, CachedList>,
+  ttl: Duration,
+}
+
+impl ListCache {
+  pub fn new(ttl: Duration) -> Self {
+    Self { lists: HashMap::new(), ttl }
+  }
+
+  /// Reads the time to live in seconds from `HN_LIST_TTL_SECS`.
+  pub fn from_env() -> Self {
+    let ttl = env::var("HN_LIST_TTL_SECS").ok().and_then(|v| v.parse().ok()).map(Duration::from_secs);
+    Self::new(ttl.unwrap_or(DEFAULT_TTL))
+  }
+
+  /// The list of `list_type`, unless its ids are older than the time to live.
+  pub fn get(&self, list_type: StoryListType) -> Option<&CachedList> {
+    self.lists.get(&list_type).filter(|list| list.fetched_at.elapsed() < self.ttl)
+  }
+
+  /// Starts a new list for freshly fetched ids, dropping what was loaded for older ones.
+  pub fn put_ids(&mut self, list_type: StoryListType, ids: Vec<u32>) {
+    let list = CachedList {
+      ids,
+      stories: Vec::new(),
+      failed: Vec::new(),
+      loaded_count: 0,
+      focused: None,
+      fetched_at: Instant::now(),
+    };
+    self.lists.insert(list_type, list);
+  }
+
+  /// Remembers how far a list was loaded when leaving its tab. Lists whose ids
+  /// were never fetched are not kept.
+  pub fn save_progress(
+    &mut self,
+    list_type: StoryListType,
+    stories: Vec<Story>,
+    failed: Vec<FailedStory>,
+    loaded_count: usize,
+    focused: Option<usize>,
+  ) {
+    if let Some(list) = self.lists.get_mut(&list_type) {
+      list.stories = stories;
+      list.failed = failed;
+      list.loaded_count = loaded_count;
+      list.focused = focused;
+    }
+  }
+
+  /// Forgets a list, so that it is fetched again, e.g. when refreshing.
+  pub fn invalidate(&mut self, list_type: StoryListType) {
+    self.lists.remove(&list_type);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn story(id: u32) -> Story {
+    Story { id, ..Story::default() }
+  }
+
+  #[test]
+  fn lists_are_kept_within_their_time_to_live() {
+    let mut cache = ListCache::new(Duration::from_secs(60));
+    cache.put_ids(StoryListType::Top, vec![1, 2, 3]);
+    cache.save_progress(StoryListType::Top, vec![story(1)], Vec::new(), 1, Some(0));
+
+    let list = cache.get(StoryListType::Top).unwrap();
+    assert_eq!(list.ids, [1, 2, 3]);
+    assert_eq!(list.stories, [story(1)]);
+    assert_eq!((list.loaded_count, list.focused), (1, Some(0)));
+    assert!(cache.get(StoryListType::New).is_none());
+  }
+
+  #[test]
+  fn lists_expire_after_their_time_to_live() {
+    let mut cache = ListCache::new(Duration::from_millis(20));
+    cache.put_ids(StoryListType::Top, vec![1]);
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(cache.get(StoryListType::Top).is_none());
+
+    // Saving progress does not extend the time to live; fetching new ids does.
+    cache.save_progress(StoryListType::Top, vec![story(1)], Vec::new(), 1, None);
+    assert!(cache.get(StoryListType::Top).is_none());
+    cache.put_ids(StoryListType::Top, vec![2]);
+    assert_eq!(cache.get(StoryListType::Top).unwrap().ids, [2]);
+  }
+
+  #[test]
+  fn a_zero_time_to_live_disables_the_cache() {
+    let mut cache = ListCache::new(Duration::ZERO);
+    cache.put_ids(StoryListType::Top, vec![1]);
+    assert!(cache.get(StoryListType::Top).is_none());
+  }
+
+  #[test]
+  fn new_ids_drop_the_progress_made_on_older_ones() {
+    let mut cache = ListCache::new(Duration::from_secs(60));
+    cache.put_ids(StoryListType::Top, vec![1, 2]);
+    cache.save_progress(StoryListType::Top, vec![story(1), story(2)], Vec::new(), 2, Some(1));
+    cache.put_ids(StoryListType::Top, vec![3]);
+    let list = cache.get(StoryListType::Top).unwrap();
+    assert!(list.stories.is_empty());
+    assert_eq!((list.loaded_count, list.focused), (0, None));
+  }
+
+  #[test]
+  fn progress_is_only_saved_for_fetched_lists() {
+    let mut cache = ListCache::new(Duration::from_secs(60));
+    cache.save_progress(StoryListType::Best, vec![story(1)], Vec::new(), 1, None);
+    assert!(cache.get(StoryListType::Best).is_none());
+  }
+
+  #[test]
+  fn invalidated_lists_are_fetched_again() {
+    let mut cache = ListCache::new(Duration::from_secs(60));
+    cache.put_ids(StoryListType::Top, vec![1]);
+    cache.put_ids(StoryListType::New, vec![2]);
+    cache.invalidate(StoryListType::Top);
+    assert!(cache.get(StoryListType::Top).is_none());
+    assert!(cache.get(StoryListType::New).is_some());
+  }
+}
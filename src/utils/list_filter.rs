@@ -0,0 +1,197 @@
+//! Sorting and filtering of the stories loaded into a list, on top of the order
+//! the API returned them in.
+
+use crate::models::Story;
+use jiff::{SignedDuration, Timestamp};
+use std::cmp::Reverse;
+use strum_macros::Display;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display)]
+pub enum StorySort {
+  /// The order of the API: the ranking of the list, or of the search.
+  #[default]
+  Ranked,
+  Score,
+  Comments,
+  Newest,
+}
+
+impl StorySort {
+  pub const ALL: [StorySort; 4] = [Self::Ranked, Self::Score, Self::Comments, Self::Newest];
+}
+
+/// How long ago stories may have been posted.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display)]
+pub enum AgeWindow {
+  #[default]
+  #[strum(to_string = "Any age")]
+  Any,
+  #[strum(to_string = "6h")]
+  SixHours,
+  #[strum(to_string = "24h")]
+  Day,
+  Week,
+}
+
+impl AgeWindow {
+  pub const ALL: [AgeWindow; 4] = [Self::Any, Self::SixHours, Self::Day, Self::Week];
+
+  fn max_age(&self) -> Option<SignedDuration> {
+    match self {
+      Self::Any => None,
+      Self::SixHours => Some(SignedDuration::from_hours(6)),
+      Self::Day => Some(SignedDuration::from_hours(24)),
+      Self::Week => Some(SignedDuration::from_hours(24 * 7)),
+    }
+  }
+}
+
+/// Whether stories linking elsewhere, self posts or both are shown.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display)]
+pub enum LinkFilter {
+  #[default]
+  All,
+  Links,
+  #[strum(to_string = "Self posts")]
+  SelfPosts,
+}
+
+impl LinkFilter {
+  pub const ALL: [LinkFilter; 3] = [Self::All, Self::Links, Self::SelfPosts];
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ListFilter {
+  pub sort: StorySort,
+  pub min_score: Option<u32>,
+  pub min_comments: Option<u32>,
+  pub age: AgeWindow,
+  pub link: LinkFilter,
+}
+
+impl ListFilter {
+  /// Whether the list is shown any different from the API order.
+  pub fn is_active(&self) -> bool {
+    self.sort != StorySort::Ranked || self.has_filters()
+  }
+
+  /// Whether any stories may be left out.
+  pub fn has_filters(&self) -> bool {
+    self.min_score.is_some()
+      || self.min_comments.is_some()
+      || self.age != AgeWindow::Any
+      || self.link != LinkFilter::All
+  }
+
+  /// Whether `story` passes the filters, as of `now`.
+  pub fn matches(&self, story: &Story, now: Timestamp) -> bool {
+    let is_recent =
+      self.age.max_age().is_none_or(|max_age| story.time.is_some_and(|time| now.duration_since(time) <= max_age));
+    let has_link = story.url.is_some();
+    self.min_score.is_none_or(|min| story.score.unwrap_or(0) >= min)
+      && self.min_comments.is_none_or(|min| story.descendants.unwrap_or(0) >= min)
+      && is_recent
+      && match self.link {
+        LinkFilter::All => true,
+        LinkFilter::Links => has_link,
+        LinkFilter::SelfPosts => !has_link,
+      }
+  }
+
+  /// The indices into `stories` of the ones that pass the filters, in the order they
+  /// are shown. The sort is stable, so stories that tie keep the API order and the
+  /// stories loaded later fall into place without reshuffling the others.
+  pub fn order(&self, stories: &[Story]) -> Vec<usize> {
+    let now = Timestamp::now();
+    let mut order: Vec<usize> = (0..stories.len()).filter(|&index| self.matches(&stories[index], now)).collect();
+    match self.sort {
+      StorySort::Ranked => {}
+      StorySort::Score => order.sort_by_key(|&index| Reverse(stories[index].score.unwrap_or(0))),
+      StorySort::Comments => order.sort_by_key(|&index| Reverse(stories[index].descendants.unwrap_or(0))),
+      StorySort::Newest => order.sort_by_key(|&index| Reverse(stories[index].time)),
+    }
+    order
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // A story posted `hours_ago`, linking elsewhere when `url` is set.
+  fn story(score: u32, comments: u32, hours_ago: i64, url: Option<&str>) -> Story {
+    Story {
+      id: score,
+      score: Some(score),
+      descendants: Some(comments),
+      time: Some(Timestamp::now() - SignedDuration::from_hours(hours_ago)),
+      url: url.map(str::to_string),
+      ..Story::default()
+    }
+  }
+
+  fn stories() -> Vec<Story> {
+    vec![
+      story(10, 50, 30, Some("https://example.com")),
+      story(300, 5, 2, None),
+      story(40, 50, 200, Some("https://example.org")),
+      story(300, 0, 10, Some("https://example.net")),
+    ]
+  }
+
+  #[test]
+  fn the_default_filter_keeps_the_api_order() {
+    let filter = ListFilter::default();
+    assert!(!filter.is_active());
+    assert_eq!(filter.order(&stories()), [0, 1, 2, 3]);
+    assert!(filter.order(&[]).is_empty());
+  }
+
+  #[test]
+  fn sorts_keep_the_api_order_of_ties() {
+    let order = |sort| ListFilter { sort, ..ListFilter::default() }.order(&stories());
+    assert_eq!(order(StorySort::Score), [1, 3, 2, 0]);
+    assert_eq!(order(StorySort::Comments), [0, 2, 1, 3]);
+    assert_eq!(order(StorySort::Newest), [1, 3, 0, 2]);
+  }
+
+  #[test]
+  fn stories_missing_counts_sort_last() {
+    let mut stories = stories();
+    stories.push(Story { id: 99, ..Story::default() });
+    let order = |sort| ListFilter { sort, ..ListFilter::default() }.order(&stories);
+    assert_eq!(order(StorySort::Score).last(), Some(&4));
+    assert_eq!(order(StorySort::Comments).last(), Some(&4));
+    assert_eq!(order(StorySort::Newest).last(), Some(&4));
+  }
+
+  #[test]
+  fn filters_leave_out_stories() {
+    let order = |filter: ListFilter| {
+      assert!(filter.has_filters());
+      filter.order(&stories())
+    };
+    assert_eq!(order(ListFilter { min_score: Some(40), ..ListFilter::default() }), [1, 2, 3]);
+    assert_eq!(order(ListFilter { min_comments: Some(5), ..ListFilter::default() }), [0, 1, 2]);
+    assert_eq!(order(ListFilter { age: AgeWindow::SixHours, ..ListFilter::default() }), [1]);
+    assert_eq!(order(ListFilter { age: AgeWindow::Day, ..ListFilter::default() }), [1, 3]);
+    assert_eq!(order(ListFilter { age: AgeWindow::Week, ..ListFilter::default() }), [0, 1, 3]);
+    assert_eq!(order(ListFilter { link: LinkFilter::Links, ..ListFilter::default() }), [0, 2, 3]);
+    assert_eq!(order(ListFilter { link: LinkFilter::SelfPosts, ..ListFilter::default() }), [1]);
+  }
+
+  #[test]
+  fn filters_and_sorts_combine() {
+    let filter =
+      ListFilter { sort: StorySort::Comments, min_score: Some(20), link: LinkFilter::Links, ..ListFilter::default() };
+    assert_eq!(filter.order(&stories()), [2, 3]);
+  }
+
+  #[test]
+  fn stories_without_a_time_are_left_out_by_age() {
+    let undated = Story { id: 1, time: None, ..Story::default() };
+    let filter = ListFilter { age: AgeWindow::Week, ..ListFilter::default() };
+    assert!(!filter.matches(&undated, Timestamp::now()));
+    assert!(ListFilter::default().matches(&undated, Timestamp::now()));
+  }
+}
@@ -1,2 +1,21 @@
 pub mod api;
+pub mod backend;
+pub mod bookmarks;
+pub mod cache;
 pub mod datetime;
+pub mod error;
+pub mod fuzzy;
+pub mod highlights;
+pub mod html;
+pub mod keymap;
+pub mod keys;
+pub mod list_cache;
+pub mod list_filter;
+pub mod mutes;
+pub mod paths;
+pub mod read_stories;
+pub mod reading_history;
+pub mod retry;
+pub mod search;
+pub mod store;
+pub mod text_rule;
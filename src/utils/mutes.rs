@@ -0,0 +1,268 @@
+//! Stories, domains, users and title patterns the user does not want to see,
+//! kept between sessions. Lists leave out the stories they match, and comments by
+//! muted users are collapsed.
+
+use crate::models::{Comment, Story};
+use crate::utils::store;
+use crate::utils::text_rule::TextRule;
+use log::warn;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+const FILE_NAME: &str = "mutes.json";
+
+/// A story hidden on its own, with its title to list it by.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HiddenStory {
+  pub id: u32,
+  pub title: Option<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Mutes {
+  stories: Vec<HiddenStory>,
+  domains: Vec<String>,
+  users: Vec<String>,
+  // Hide the stories whose title they match.
+  title_rules: Vec<TextRule>,
+  // The title rules, compiled.
+  #[serde(skip)]
+  patterns: Vec<Regex>,
+}
+
+impl Mutes {
+  /// Reads the mutes from the data directory. Patterns that no longer compile are
+  /// kept but match nothing.
+  pub fn load() -> Self {
+    let mut mutes: Self = store::load(FILE_NAME);
+    mutes.compile_patterns();
+    mutes
+  }
+
+  pub fn stories(&self) -> &[HiddenStory] {
+    &self.stories
+  }
+
+  pub fn domains(&self) -> &[String] {
+    &self.domains
+  }
+
+  pub fn users(&self) -> &[String] {
+    &self.users
+  }
+
+  pub fn title_rules(&self) -> &[TextRule] {
+    &self.title_rules
+  }
+
+  /// Whether lists leave out `story`: it was hidden, links to a muted domain or one
+  /// of its subdomains, was posted by a muted user or has a muted title.
+  pub fn hides_story(&self, story: &Story) -> bool {
+    self.stories.iter().any(|hidden| hidden.id == story.id)
+      || story.domain().is_some_and(|domain| self.mutes_domain(domain))
+      || self.mutes_user(story.by.as_deref())
+      || story.title.as_deref().is_some_and(|title| self.mutes_title(title))
+  }
+
+  /// Whether `comment` was posted by a muted user.
+  pub fn hides_comment(&self, comment: &Comment) -> bool {
+    self.mutes_user(comment.by.as_deref())
+  }
+
+  pub fn mutes_user(&self, name: Option<&str>) -> bool {
+    name.is_some_and(|name| self.users.iter().any(|user| user == name))
+  }
+
+  fn mutes_domain(&self, domain: &str) -> bool {
+    self.domains.iter().any(|muted| {
+      domain.eq_ignore_ascii_case(muted)
+        || domain.len() > muted.len()
+          && domain.to_ascii_lowercase().ends_with(&format!(".{}", muted.to_ascii_lowercase()))
+    })
+  }
+
+  fn mutes_title(&self, title: &str) -> bool {
+    self.patterns.iter().any(|pattern| pattern.is_match(title))
+  }
+
+  pub fn hide_story(&mut self, story: &Story) {
+    if !self.stories.iter().any(|hidden| hidden.id == story.id) {
+      self.stories.push(HiddenStory { id: story.id, title: story.title.clone() });
+      self.save();
+    }
+  }
+
+  pub fn unhide_story(&mut self, id: u32) {
+    self.stories.retain(|hidden| hidden.id != id);
+    self.save();
+  }
+
+  pub fn mute_domain(&mut self, domain: &str) {
+    let domain = domain.trim().to_ascii_lowercase();
+    if !domain.is_empty() && !self.domains.contains(&domain) {
+      self.domains.push(domain);
+      self.save();
+    }
+  }
+
+  pub fn unmute_domain(&mut self, domain: &str) {
+    self.domains.retain(|muted| muted != domain);
+    self.save();
+  }
+
+  pub fn mute_user(&mut self, name: &str) {
+    let name = name.trim();
+    if !name.is_empty() && !self.users.iter().any(|user| user == name) {
+      self.users.push(name.to_string());
+      self.save();
+    }
+  }
+
+  pub fn unmute_user(&mut self, name: &str) {
+    self.users.retain(|user| user != name);
+    self.save();
+  }
+
+  /// Adds a title rule, unless its pattern is not a valid regular expression.
+  pub fn add_title_rule(&mut self, rule: TextRule) -> Result<(), regex::Error> {
+    rule.compile()?;
+    if !self.title_rules.contains(&rule) {
+      self.title_rules.push(rule);
+      self.compile_patterns();
+      self.save();
+    }
+    Ok(())
+  }
+
+  pub fn remove_title_rule(&mut self, index: usize) {
+    if index < self.title_rules.len() {
+      self.title_rules.remove(index);
+      self.compile_patterns();
+      self.save();
+    }
+  }
+
+  fn compile_patterns(&mut self) {
+    self.patterns = self
+      .title_rules
+      .iter()
+      .filter_map(|rule| rule.compile().inspect_err(|e| warn!("Ignoring invalid title rule {rule}: {e}")).ok())
+      .collect();
+  }
+
+  fn save(&self) {
+    store::save_later(FILE_NAME, self.clone());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dioxus::dioxus_core::{ScopeId, VNode, VirtualDom};
+  use serde_json::json;
+
+  fn muting(domains: &[&str]) -> Mutes {
+    let mut mutes = Mutes::default();
+    for domain in domains {
+      mutes.mute_domain(domain);
+    }
+    mutes
+  }
+
+  fn story(url: Option<&str>, by: Option<&str>) -> Story {
+    Story { id: 1, url: url.map(str::to_string), by: by.map(str::to_string), ..Story::default() }
+  }
+
+  fn titled(title: &str) -> Story {
+    Story { id: 1, title: Some(title.to_string()), ..Story::default() }
+  }
+
+  #[test]
+  fn domains_are_muted_with_their_subdomains() {
+    let mutes = muting(&["Example.com"]);
+    assert!(mutes.mutes_domain("example.com"));
+    assert!(mutes.mutes_domain("EXAMPLE.COM"));
+    assert!(mutes.mutes_domain("blog.example.com"));
+    assert!(mutes.mutes_domain("a.b.example.com"));
+  }
+
+  #[test]
+  fn similar_domains_are_not_muted() {
+    let mutes = muting(&["example.com"]);
+    assert!(!mutes.mutes_domain("notexample.com"));
+    assert!(!mutes.mutes_domain("example.com.evil.org"));
+    assert!(!mutes.mutes_domain("com"));
+    assert!(!muting(&[]).mutes_domain("example.com"));
+  }
+
+  #[test]
+  fn stories_are_hidden_by_domain_and_user() {
+    let mut mutes = muting(&["example.com"]);
+    mutes.mute_user("spammer");
+    assert!(mutes.hides_story(&story(Some("https://www.example.com/post"), None)));
+    assert!(mutes.hides_story(&story(Some("https://news.example.com"), None)));
+    assert!(!mutes.hides_story(&story(Some("https://example.org"), Some("pg"))));
+    // Self posts have no domain to mute.
+    assert!(!mutes.hides_story(&story(None, None)));
+    assert!(mutes.hides_story(&story(None, Some("spammer"))));
+  }
+
+  #[test]
+  fn muted_domains_are_trimmed_and_kept_once() {
+    let mut mutes = muting(&[" Example.com ", "example.com", "  "]);
+    assert_eq!(mutes.domains(), ["example.com"]);
+    mutes.unmute_domain("example.com");
+    assert!(mutes.domains().is_empty());
+  }
+
+  #[test]
+  fn title_keywords_are_matched_ignoring_case() {
+    let mut mutes = Mutes::default();
+    mutes.add_title_rule(TextRule::Keyword("crypto".to_string())).unwrap();
+    assert!(mutes.hides_story(&titled("The CRYPTO winter")));
+    assert!(mutes.hides_story(&titled("Cryptography basics")));
+    assert!(!mutes.hides_story(&titled("Rust 2.0")));
+    // Keywords match literally, not as patterns.
+    mutes.add_title_rule(TextRule::Keyword("a.b".to_string())).unwrap();
+    assert!(!mutes.hides_story(&titled("axb")));
+    assert!(!mutes.hides_story(&Story::default()));
+  }
+
+  #[test]
+  fn title_patterns_are_matched_as_regular_expressions() {
+    let mut mutes = Mutes::default();
+    mutes.add_title_rule(TextRule::Regex(r"^(ask|tell) hn:".to_string())).unwrap();
+    assert!(mutes.hides_story(&titled("Ask HN: Who is hiring?")));
+    assert!(mutes.hides_story(&titled("tell hn: a story")));
+    assert!(!mutes.hides_story(&titled("Show HN: My project")));
+    mutes.remove_title_rule(0);
+    assert!(!mutes.hides_story(&titled("Ask HN: Who is hiring?")));
+  }
+
+  #[test]
+  fn invalid_title_patterns_are_rejected() {
+    let mut mutes = Mutes::default();
+    assert!(mutes.add_title_rule(TextRule::Regex("(unclosed".to_string())).is_err());
+    assert!(mutes.title_rules().is_empty());
+    assert!(!mutes.hides_story(&titled("(unclosed")));
+  }
+
+  #[test]
+  fn comments_by_muted_users_are_hidden() {
+    let mut mutes = Mutes::default();
+    mutes.mute_user("spammer");
+    // Comments hold signals, which need a scope to be created in.
+    let dom = VirtualDom::new(VNode::empty);
+    dom.in_runtime(|| {
+      ScopeId::ROOT.in_runtime(|| {
+        let comment = |by: Option<&str>| -> Comment {
+          serde_json::from_value(json!({ "id": 1, "type": "comment", "by": by, "parent": 100 })).unwrap()
+        };
+        assert!(mutes.hides_comment(&comment(Some("spammer"))));
+        assert!(!mutes.hides_comment(&comment(Some("pg"))));
+        assert!(!mutes.hides_comment(&comment(None)));
+      })
+    });
+  }
+}
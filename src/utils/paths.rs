@@ -0,0 +1,59 @@
+//! Resolves the per-user directories the application stores its files in.
+
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR_NAME: &str = "freya-hn-app";
+
+/// Returns the application's cache directory, following the XDG convention on Linux
+/// (`$XDG_CACHE_HOME` or `~/.cache`) and the platform equivalents elsewhere.
+pub fn cache_dir() -> Option<PathBuf> {
+  let base = if cfg!(target_os = "windows") {
+    env_dir("LOCALAPPDATA")
+  } else if cfg!(target_os = "macos") {
+    home_dir().map(|home| home.join("Library").join("Caches"))
+  } else {
+    env_dir("XDG_CACHE_HOME").or_else(|| home_dir().map(|home| home.join(".cache")))
+  };
+  base.map(|dir| dir.join(APP_DIR_NAME))
+}
+
+/// Returns the application's configuration directory, following the XDG convention on
+/// Linux (`$XDG_CONFIG_HOME` or `~/.config`) and the platform equivalents elsewhere.
+pub fn config_dir() -> Option<PathBuf> {
+  let base = if cfg!(target_os = "windows") {
+    env_dir("APPDATA")
+  } else if cfg!(target_os = "macos") {
+    home_dir().map(|home| home.join("Library").join("Application Support"))
+  } else {
+    env_dir("XDG_CONFIG_HOME").or_else(|| home_dir().map(|home| home.join(".config")))
+  };
+  base.map(|dir| dir.join(APP_DIR_NAME))
+}
+
+/// Returns the directory for state the application keeps between sessions, following
+/// the XDG convention on Linux (`$XDG_DATA_HOME` or `~/.local/share`) and the platform
+/// equivalents elsewhere. Tests have none, so that they never touch the user's state.
+pub fn data_dir() -> Option<PathBuf> {
+  if cfg!(test) {
+    return None;
+  }
+  let base = if cfg!(target_os = "windows") {
+    env_dir("APPDATA")
+  } else if cfg!(target_os = "macos") {
+    home_dir().map(|home| home.join("Library").join("Application Support"))
+  } else {
+    env_dir("XDG_DATA_HOME").or_else(|| home_dir().map(|home| home.join(".local").join("share")))
+  };
+  base.map(|dir| dir.join(APP_DIR_NAME))
+}
+
+fn home_dir() -> Option<PathBuf> {
+  env_dir("HOME").or_else(|| env_dir("USERPROFILE"))
+}
+
+// Reads a directory from an environment variable, ignoring empty and relative values
+// as the XDG specification requires.
+fn env_dir(key: &str) -> Option<PathBuf> {
+  env::var_os(key).map(PathBuf::from).filter(|path| path.is_absolute())
+}
@@ -0,0 +1,183 @@
+//! The stories the user has opened, kept between sessions so that lists can tell
+//! read stories apart or hide them, and the comments shown in them, so that
+//! reopening a story can point out the new ones.
+
+use crate::models::{Comment, Story};
+use crate::utils::store;
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+const FILE_NAME: &str = "read_stories.json";
+// The oldest visits are forgotten beyond this many read stories.
+const MAX_VISITS: usize = 10_000;
+// Holds a file of seen comments per story, named after its id.
+const SEEN_COMMENTS_DIR: &str = "seen_comments";
+
+/// When a story was last opened and how many comments it had then.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Visit {
+  pub at: Timestamp,
+  pub comment_count: u32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadStories {
+  visits: HashMap<u32, Visit>,
+  hide_read: bool,
+  // The ids in `visits`, oldest visit first, to find the one to forget.
+  #[serde(skip)]
+  by_age: BTreeSet<(Timestamp, u32)>,
+}
+
+impl ReadStories {
+  /// Reads the visits of previous sessions from the data directory.
+  pub fn load() -> Self {
+    let mut read_stories: Self = store::load(FILE_NAME);
+    read_stories.by_age = read_stories.visits.iter().map(|(id, visit)| (visit.at, *id)).collect();
+    read_stories
+  }
+
+  pub fn is_read(&self, id: u32) -> bool {
+    self.visits.contains_key(&id)
+  }
+
+  /// How many comments `story` gained since its last visit, or zero if it was
+  /// never opened.
+  pub fn new_comment_count(&self, story: &Story) -> u32 {
+    let Some(visit) = self.visits.get(&story.id) else { return 0 };
+    story.descendants.unwrap_or(0).saturating_sub(visit.comment_count)
+  }
+
+  /// Whether lists leave out read stories.
+  pub fn hide_read(&self) -> bool {
+    self.hide_read
+  }
+
+  /// Whether lists leave out the story with this id.
+  pub fn hides(&self, id: u32) -> bool {
+    self.hide_read && self.is_read(id)
+  }
+
+  /// Records a visit to `story` now, replacing the previous one.
+  pub fn mark_read(&mut self, story: &Story) {
+    if let Some(forgotten) = self.visit(story, Timestamp::now()) {
+      store::remove(&SeenComments::file_name(forgotten));
+    }
+    self.save();
+  }
+
+  // Records a visit at `at`, returning the id of the story forgotten to make room.
+  fn visit(&mut self, story: &Story, at: Timestamp) -> Option<u32> {
+    let visit = Visit { at, comment_count: story.descendants.unwrap_or(0) };
+    if let Some(previous) = self.visits.insert(story.id, visit) {
+      self.by_age.remove(&(previous.at, story.id));
+    }
+    self.by_age.insert((at, story.id));
+    if self.visits.len() <= MAX_VISITS {
+      return None;
+    }
+    let (_, oldest) = self.by_age.pop_first()?;
+    self.visits.remove(&oldest);
+    Some(oldest)
+  }
+
+  pub fn set_hide_read(&mut self, hide_read: bool) {
+    self.hide_read = hide_read;
+    self.save();
+  }
+
+  fn save(&self) {
+    store::save_later(FILE_NAME, self.clone());
+  }
+}
+
+/// The comments of a story shown in its earlier visits.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenComments {
+  first_visit: Timestamp,
+  ids: HashSet<u32>,
+}
+
+impl SeenComments {
+  /// The comments seen in the earlier visits of a story, or `None` if this is
+  /// its first visit.
+  pub fn load(story_id: u32) -> Option<Self> {
+    store::load(&Self::file_name(story_id))
+  }
+
+  /// Whether a comment is new to the user: posted after the story was first
+  /// opened and not shown since. Older comments in threads that were never
+  /// expanded do not count as new.
+  pub fn is_new(&self, comment: &Comment) -> bool {
+    !self.ids.contains(&comment.id) && comment.time.is_some_and(|time| time > self.first_visit)
+  }
+
+  /// The comments seen in the first visit of a story, which starts now.
+  pub fn first_visit() -> Self {
+    Self { first_visit: Timestamp::now(), ids: HashSet::new() }
+  }
+
+  /// Adds the comments shown in the current visit of a story, saving them in the
+  /// background if any were not seen before.
+  pub fn record(&mut self, story_id: u32, ids: impl IntoIterator<Item = u32>) {
+    let count = self.ids.len();
+    self.ids.extend(ids);
+    if self.ids.len() != count || count == 0 {
+      store::save_later(&Self::file_name(story_id), self.clone());
+    }
+  }
+
+  fn file_name(story_id: u32) -> String {
+    format!("{SEEN_COMMENTS_DIR}/{story_id}.json")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn story(id: u32, descendants: u32) -> Story {
+    Story { id, descendants: Some(descendants), ..Story::default() }
+  }
+
+  fn at(second: i64) -> Timestamp {
+    Timestamp::from_second(second).unwrap()
+  }
+
+  #[test]
+  fn visits_count_new_comments() {
+    let mut read_stories = ReadStories::default();
+    assert_eq!(read_stories.visit(&story(1, 10), at(1)), None);
+    assert_eq!(read_stories.visits[&1], Visit { at: at(1), comment_count: 10 });
+    assert!(read_stories.is_read(1));
+    assert!(!read_stories.is_read(2));
+    assert_eq!(read_stories.new_comment_count(&story(1, 14)), 4);
+    assert_eq!(read_stories.new_comment_count(&story(2, 14)), 0);
+  }
+
+  #[test]
+  fn the_oldest_visit_is_forgotten_beyond_the_limit() {
+    let mut read_stories = ReadStories::default();
+    for id in 0..MAX_VISITS as u32 {
+      assert_eq!(read_stories.visit(&story(id, 0), at(i64::from(id) + 10)), None);
+    }
+    // Visiting the oldest story again makes the second oldest the one to forget.
+    assert_eq!(read_stories.visit(&story(0, 0), at(100_000)), None);
+    assert_eq!(read_stories.visit(&story(u32::MAX, 0), at(100_001)), Some(1));
+    assert_eq!(read_stories.visits.len(), MAX_VISITS);
+    assert_eq!(read_stories.by_age.len(), MAX_VISITS);
+    assert!(read_stories.is_read(0) && !read_stories.is_read(1));
+  }
+
+  #[test]
+  fn hiding_applies_to_read_stories_only() {
+    let mut read_stories = ReadStories::default();
+    read_stories.visit(&story(1, 0), at(1));
+    assert!(!read_stories.hides(1));
+    read_stories.hide_read = true;
+    assert!(read_stories.hides(1));
+    assert!(!read_stories.hides(2));
+  }
+}
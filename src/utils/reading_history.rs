@@ -0,0 +1,154 @@
+//! Every story and profile the user has opened, newest first, kept between
+//! sessions for the history view.
+
+use crate::models::{Story, domain};
+use crate::utils::datetime::local_date;
+use crate::utils::store;
+use jiff::Timestamp;
+use jiff::civil::Date;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+const FILE_NAME: &str = "reading_history.json";
+// The oldest entries are forgotten beyond this many.
+const MAX_ENTRIES: usize = 10_000;
+
+/// A page opened, with what is needed to list it and open it again.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Visited {
+  Story { id: u32, title: Option<String>, url: Option<String> },
+  User(String),
+}
+
+impl From<&Story> for Visited {
+  fn from(story: &Story) -> Self {
+    Visited::Story { id: story.id, title: story.title.clone(), url: story.url.clone() }
+  }
+}
+
+impl Visited {
+  /// Whether `filter`, in lowercase, is part of the story's title or domain, or
+  /// of the username.
+  pub fn matches(&self, filter: &str) -> bool {
+    match self {
+      Visited::Story { title, url, .. } => {
+        title.as_ref().is_some_and(|title| title.to_lowercase().contains(filter))
+          || url.as_deref().and_then(domain).is_some_and(|domain| domain.to_lowercase().contains(filter))
+      }
+      Visited::User(name) => name.to_lowercase().contains(filter),
+    }
+  }
+
+  // Whether both are the same page, even if a story's title changed since.
+  fn is_same_page(&self, other: &Visited) -> bool {
+    match (self, other) {
+      (Visited::Story { id, .. }, Visited::Story { id: other_id, .. }) => id == other_id,
+      (Visited::User(name), Visited::User(other_name)) => name == other_name,
+      _ => false,
+    }
+  }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+  pub visited: Visited,
+  pub at: Timestamp,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadingHistory {
+  // Most recent first.
+  entries: Vec<HistoryEntry>,
+}
+
+impl ReadingHistory {
+  /// Reads the history of previous sessions from the data directory.
+  pub fn load() -> Self {
+    store::load(FILE_NAME)
+  }
+
+  pub fn entries(&self) -> &[HistoryEntry] {
+    &self.entries
+  }
+
+  /// Records opening a page now. A page opened again on the same day moves to the
+  /// top instead of being listed twice.
+  pub fn record(&mut self, visited: Visited) {
+    self.record_at(visited, Timestamp::now());
+    self.save();
+  }
+
+  fn record_at(&mut self, visited: Visited, at: Timestamp) {
+    let today = local_date(&at);
+    self.entries.retain(|entry| !(entry.visited.is_same_page(&visited) && local_date(&entry.at) == today));
+    self.entries.insert(0, HistoryEntry { visited, at });
+    self.entries.truncate(MAX_ENTRIES);
+  }
+
+  /// Forgets the pages opened on the days in `days`.
+  pub fn clear(&mut self, days: RangeInclusive<Date>) {
+    self.entries.retain(|entry| !days.contains(&local_date(&entry.at)));
+    self.save();
+  }
+
+  // Written in the background, as it changes with every page opened.
+  fn save(&self) {
+    store::save_later(FILE_NAME, self.clone());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use jiff::ToSpan;
+
+  fn story(id: u32) -> Visited {
+    Visited::Story { id, title: Some(format!("Story {id}")), url: None }
+  }
+
+  fn days_ago(days: i64) -> Timestamp {
+    Timestamp::now() - (days * 24).hours()
+  }
+
+  fn ids(history: &ReadingHistory) -> Vec<Visited> {
+    history.entries().iter().map(|entry| entry.visited.clone()).collect()
+  }
+
+  #[test]
+  fn pages_opened_again_on_the_same_day_move_to_the_top() {
+    let mut history = ReadingHistory::default();
+    history.record_at(story(1), days_ago(3));
+    history.record_at(story(1), days_ago(0));
+    history.record_at(story(2), days_ago(0));
+    history.record_at(Visited::User("pg".to_string()), days_ago(0));
+    history.record_at(story(1), days_ago(0));
+    assert_eq!(ids(&history), [story(1), Visited::User("pg".to_string()), story(2), story(1)]);
+  }
+
+  #[test]
+  fn clearing_forgets_the_days_in_the_range() {
+    let mut history = ReadingHistory::default();
+    for days in [40, 8, 7, 0] {
+      history.record_at(story(days as u32), days_ago(days));
+    }
+    let today = local_date(&Timestamp::now());
+    history.clear(today.saturating_sub(10.days())..=today.saturating_sub(5.days()));
+    assert_eq!(ids(&history), [story(0), story(40)]);
+    history.clear(Date::MIN..=Date::MAX);
+    assert!(history.entries().is_empty());
+  }
+
+  #[test]
+  fn filters_match_titles_domains_and_usernames() {
+    let visited = Visited::Story {
+      id: 1,
+      title: Some("Show HN: Freya".to_string()),
+      url: Some("https://www.example.com/a".to_string()),
+    };
+    assert!(visited.matches("freya"));
+    assert!(visited.matches("example.com"));
+    assert!(!visited.matches("www."));
+    assert!(Visited::User("dang".to_string()).matches("an"));
+  }
+}
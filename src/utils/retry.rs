@@ -0,0 +1,118 @@
+//! Retrying of transient API failures with exponential backoff and jitter.
+
+use crate::utils::error::ApiError;
+use log::warn;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+  /// Total number of attempts, including the first one.
+  pub max_attempts: u32,
+  /// Delay before the first retry; doubled for every further one.
+  pub base_delay: Duration,
+  /// Upper bound for a single delay.
+  pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self { max_attempts: 4, base_delay: Duration::from_millis(250), max_delay: Duration::from_secs(4) }
+  }
+}
+
+impl RetryPolicy {
+  /// A policy that makes a single attempt.
+  pub fn none() -> Self {
+    Self { max_attempts: 1, ..Self::default() }
+  }
+
+  // The delay before retry number `retry` (0-based): the exponential backoff,
+  // randomized into its upper half so that parallel requests do not retry in lockstep.
+  fn delay_for(&self, retry: u32) -> Duration {
+    let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(retry)).min(self.max_delay);
+    let millis = backoff.as_millis() as u64;
+    Duration::from_millis(millis / 2 + fastrand::u64(0..=millis / 2))
+  }
+
+  /// Runs `operation` until it succeeds, fails with a non-retryable error or the
+  /// attempt budget is used up.
+  pub async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T, ApiError>
+  where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+  {
+    let mut attempt = 1;
+    loop {
+      match operation().await {
+        Err(e) if e.is_retryable() && attempt < self.max_attempts => {
+          let delay = self.delay_for(attempt - 1);
+          warn!("Attempt {attempt}/{} failed ({e}), retrying in {delay:?}", self.max_attempts);
+          tokio::time::sleep(delay).await;
+          attempt += 1;
+        }
+        result => return result,
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::cell::Cell;
+
+  fn fast(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy { max_attempts, base_delay: Duration::from_millis(1), max_delay: Duration::from_millis(2) }
+  }
+
+  // Runs `policy` over an operation failing with `error` `failures` times, returning
+  // the result and how many attempts were made.
+  async fn attempts(policy: RetryPolicy, error: ApiError, failures: u32) -> (Result<u32, ApiError>, u32) {
+    let calls = Cell::new(0);
+    let result = policy
+      .run(|| {
+        calls.set(calls.get() + 1);
+        let result = if calls.get() <= failures { Err(error.clone()) } else { Ok(calls.get()) };
+        async move { result }
+      })
+      .await;
+    (result, calls.get())
+  }
+
+  #[tokio::test]
+  async fn transient_failures_are_retried_until_success() {
+    assert_eq!(attempts(fast(4), ApiError::Timeout, 2).await, (Ok(3), 3));
+  }
+
+  #[tokio::test]
+  async fn retries_stop_at_max_attempts() {
+    assert_eq!(attempts(fast(3), ApiError::Http(503), 10).await, (Err(ApiError::Http(503)), 3));
+  }
+
+  #[tokio::test]
+  async fn permanent_failures_are_not_retried() {
+    assert_eq!(attempts(fast(4), ApiError::NotFound, 10).await, (Err(ApiError::NotFound), 1));
+  }
+
+  #[tokio::test]
+  async fn the_none_policy_makes_a_single_attempt() {
+    assert_eq!(attempts(RetryPolicy::none(), ApiError::Timeout, 10).await, (Err(ApiError::Timeout), 1));
+  }
+
+  #[test]
+  fn delays_double_within_their_upper_half() {
+    let policy = RetryPolicy::default();
+    for (retry, backoff) in [(0, 250), (1, 500), (2, 1000)] {
+      let delay = policy.delay_for(retry).as_millis();
+      assert!((backoff / 2..=backoff).contains(&delay), "retry {retry}: {delay}ms");
+    }
+  }
+
+  #[test]
+  fn delays_are_capped_at_max_delay() {
+    let policy = RetryPolicy::default();
+    for retry in [5, 10, 31, 64] {
+      assert!(policy.delay_for(retry) <= policy.max_delay, "retry {retry}");
+    }
+  }
+}
@@ -0,0 +1,416 @@
+//! Full-text search through the Algolia Hacker News Search API.
+
+use crate::models::Story;
+use crate::utils::error::ApiError;
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, Timestamp};
+use reqwest::Client;
+use serde::Deserialize;
+use std::env;
+use strum_macros::Display;
+
+const HN_SEARCH_BASE_URL: &str = "https://hn.algolia.com/api/v1/";
+
+// --- Query Types ---
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display)]
+pub enum SearchTag {
+  #[default]
+  All,
+  Story,
+  Comment,
+  #[strum(to_string = "Ask HN")]
+  AskHn,
+  #[strum(to_string = "Show HN")]
+  ShowHn,
+  Poll,
+}
+
+impl SearchTag {
+  pub const ALL: [SearchTag; 6] = [Self::All, Self::Story, Self::Comment, Self::AskHn, Self::ShowHn, Self::Poll];
+
+  fn as_api_str(&self) -> Option<&'static str> {
+    match self {
+      Self::All => None,
+      Self::Story => Some("story"),
+      Self::Comment => Some("comment"),
+      Self::AskHn => Some("ask_hn"),
+      Self::ShowHn => Some("show_hn"),
+      Self::Poll => Some("poll"),
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display)]
+pub enum SearchSort {
+  #[default]
+  Relevance,
+  Date,
+}
+
+/// How far back results may go, relative to now, or the days they were posted on.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Display)]
+pub enum DateRange {
+  #[default]
+  #[strum(to_string = "All time")]
+  AllTime,
+  #[strum(to_string = "24h")]
+  Day,
+  Week,
+  Month,
+  Year,
+  /// From the start of `from` through the end of `to`, in the local time zone;
+  /// either end may be left open.
+  #[strum(to_string = "Between")]
+  Between {
+    from: Option<Date>,
+    to: Option<Date>,
+  },
+}
+
+impl DateRange {
+  /// The ranges relative to now.
+  pub const ALL: [DateRange; 5] = [Self::AllTime, Self::Day, Self::Week, Self::Month, Self::Year];
+
+  fn max_age(&self) -> Option<SignedDuration> {
+    match self {
+      Self::AllTime | Self::Between { .. } => None,
+      Self::Day => Some(SignedDuration::from_hours(24)),
+      Self::Week => Some(SignedDuration::from_hours(24 * 7)),
+      Self::Month => Some(SignedDuration::from_hours(24 * 30)),
+      Self::Year => Some(SignedDuration::from_hours(24 * 365)),
+    }
+  }
+
+  // The first second results may have been posted at and the first one past the
+  // range, as of `now`.
+  fn bounds(&self, now: Timestamp) -> (Option<i64>, Option<i64>) {
+    let start_of = |date: Date| date.to_zoned(TimeZone::system()).ok().map(|day| day.timestamp().as_second());
+    match *self {
+      Self::Between { from, to } => (from.and_then(start_of), to.and_then(|to| to.tomorrow().ok()).and_then(start_of)),
+      _ => (self.max_age().map(|max_age| now.as_second() - max_age.as_secs()), None),
+    }
+  }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SearchQuery {
+  pub text: String,
+  pub tag: SearchTag,
+  pub author: Option<String>,
+  pub date_range: DateRange,
+  pub min_points: Option<u32>,
+  pub sort: SearchSort,
+}
+
+impl SearchQuery {
+  // Builds the Algolia query string parameters for one page of results.
+  fn to_params(&self, page: usize, hits_per_page: usize) -> Vec<(&'static str, String)> {
+    let mut params = vec![("query", self.text.clone()), ("page", page.to_string())];
+    params.push(("hitsPerPage", hits_per_page.to_string()));
+
+    // Tags are ANDed: the item type and the author must both match.
+    let mut tags: Vec<String> = self.tag.as_api_str().map(str::to_string).into_iter().collect();
+    if let Some(author) = self.author.as_deref().filter(|a| !a.is_empty()) {
+      tags.push(format!("author_{author}"));
+    }
+    if !tags.is_empty() {
+      params.push(("tags", tags.join(",")));
+    }
+
+    let mut numeric_filters = Vec::new();
+    let (since, until) = self.date_range.bounds(Timestamp::now());
+    if let Some(since) = since {
+      numeric_filters.push(format!("created_at_i>={since}"));
+    }
+    if let Some(until) = until {
+      numeric_filters.push(format!("created_at_i<{until}"));
+    }
+    if let Some(min_points) = self.min_points {
+      numeric_filters.push(format!("points>={min_points}"));
+    }
+    if !numeric_filters.is_empty() {
+      params.push(("numericFilters", numeric_filters.join(",")));
+    }
+    params
+  }
+}
+
+// --- Response Types ---
+#[derive(Deserialize)]
+struct SearchResponse {
+  hits: Vec<SearchHit>,
+  page: usize,
+  #[serde(rename = "nbPages")]
+  nb_pages: usize,
+}
+
+#[derive(Deserialize)]
+struct SearchHit {
+  #[serde(rename = "objectID")]
+  object_id: String,
+  title: Option<String>,
+  url: Option<String>,
+  author: Option<String>,
+  points: Option<u32>,
+  // The body of self posts, as HTML.
+  story_text: Option<String>,
+  num_comments: Option<u32>,
+  created_at_i: Option<i64>,
+  // Only set on comment hits.
+  story_id: Option<u32>,
+  story_title: Option<String>,
+  story_url: Option<String>,
+}
+
+impl SearchHit {
+  // Comment hits are shown as the story they were posted in, so opening one shows the thread.
+  fn into_story(self) -> Option<Story> {
+    let time = self.created_at_i.and_then(|secs| Timestamp::from_second(secs).ok());
+    let story = match (self.title, self.story_id) {
+      (Some(title), _) => Story {
+        id: self.object_id.parse().ok()?,
+        title: Some(title),
+        url: self.url,
+        by: self.author,
+        score: self.points,
+        time,
+        text: self.story_text,
+        descendants: self.num_comments,
+        ..Default::default()
+      },
+      (None, Some(story_id)) => Story {
+        id: story_id,
+        title: self
+          .story_title
+          .map(|title| format!("Comment by {} on: {title}", self.author.as_deref().unwrap_or("?"))),
+        url: self.story_url,
+        by: self.author,
+        time,
+        ..Default::default()
+      },
+      (None, None) => return None,
+    };
+    Some(story)
+  }
+}
+
+/// One page of search results.
+pub struct SearchPage {
+  pub stories: Vec<Story>,
+  pub has_more: bool,
+}
+
+#[derive(Clone)]
+pub struct SearchService {
+  client: Client,
+  base_url: String,
+}
+
+impl SearchService {
+  /// Creates the service, using `HN_SEARCH_BASE_URL` instead of the public
+  /// Algolia endpoint when set (e.g. to test against a local stand-in server).
+  pub fn from_env() -> Self {
+    Self::with_base_url(env::var("HN_SEARCH_BASE_URL").unwrap_or_else(|_| HN_SEARCH_BASE_URL.to_string()))
+  }
+
+  pub fn with_base_url(base_url: impl Into<String>) -> Self {
+    let mut base_url = base_url.into();
+    if !base_url.ends_with('/') {
+      base_url.push('/');
+    }
+    Self { client: Client::new(), base_url }
+  }
+
+  pub async fn search(&self, query: &SearchQuery, page: usize, hits_per_page: usize) -> Result<SearchPage, ApiError> {
+    let endpoint = match query.sort {
+      SearchSort::Relevance => "search",
+      SearchSort::Date => "search_by_date",
+    };
+    let response = self
+      .client
+      .get(format!("{}{}", self.base_url, endpoint))
+      .query(&query.to_params(page, hits_per_page))
+      .send()
+      .await?
+      .error_for_status()?
+      .json::<SearchResponse>()
+      .await?;
+    Ok(SearchPage {
+      has_more: response.page + 1 < response.nb_pages,
+      stories: response.hits.into_iter().filter_map(SearchHit::into_story).collect(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+
+  fn param<'a>(params: &'a [(&str, String)], name: &str) -> Option<&'a str> {
+    params.iter().find(|(key, _)| *key == name).map(|(_, value)| value.as_str())
+  }
+
+  #[test]
+  fn params_carry_the_query_page_and_tags() {
+    let query = SearchQuery {
+      text: "rust gui".to_string(),
+      tag: SearchTag::ShowHn,
+      author: Some("alice".to_string()),
+      min_points: Some(10),
+      ..Default::default()
+    };
+    let params = query.to_params(2, 20);
+
+    assert_eq!(param(&params, "query"), Some("rust gui"));
+    assert_eq!(param(&params, "page"), Some("2"));
+    assert_eq!(param(&params, "hitsPerPage"), Some("20"));
+    assert_eq!(param(&params, "tags"), Some("show_hn,author_alice"));
+    assert_eq!(param(&params, "numericFilters"), Some("points>=10"));
+  }
+
+  #[test]
+  fn params_leave_out_unused_filters() {
+    let params = SearchQuery::default().to_params(0, 20);
+    assert_eq!(param(&params, "tags"), None);
+    assert_eq!(param(&params, "numericFilters"), None);
+  }
+
+  #[test]
+  fn a_relative_date_range_starts_that_long_ago() {
+    let now = Timestamp::now();
+    let (since, until) = DateRange::Week.bounds(now);
+    assert_eq!(since, Some(now.as_second() - 7 * 24 * 60 * 60));
+    assert_eq!(until, None);
+    assert_eq!(DateRange::AllTime.bounds(now), (None, None));
+  }
+
+  #[test]
+  fn an_explicit_date_range_covers_whole_days() {
+    let day = |text: &str| text.parse::<Date>().unwrap();
+    let start_of = |text: &str| day(text).to_zoned(TimeZone::system()).unwrap().timestamp().as_second();
+    let range = DateRange::Between { from: Some(day("2024-03-01")), to: Some(day("2024-03-31")) };
+    let params = SearchQuery { date_range: range, ..Default::default() }.to_params(0, 20);
+
+    let expected = format!("created_at_i>={},created_at_i<{}", start_of("2024-03-01"), start_of("2024-04-01"));
+    assert_eq!(param(&params, "numericFilters"), Some(expected.as_str()));
+  }
+
+  #[test]
+  fn an_explicit_date_range_may_be_open_ended() {
+    let from = "2024-03-01".parse::<Date>().unwrap();
+    let (since, until) = DateRange::Between { from: Some(from), to: None }.bounds(Timestamp::now());
+    assert!(since.is_some());
+    assert_eq!(until, None);
+  }
+
+  fn hit(value: serde_json::Value) -> SearchHit {
+    serde_json::from_value(value).unwrap()
+  }
+
+  #[test]
+  fn story_hits_map_to_stories() {
+    let story = hit(json!({
+      "objectID": "42", "title": "A story", "url": "https://example.com", "author": "alice",
+      "points": 12, "num_comments": 3, "created_at_i": 1700000000
+    }))
+    .into_story()
+    .unwrap();
+
+    assert_eq!(story.id, 42);
+    assert_eq!(story.title.as_deref(), Some("A story"));
+    assert_eq!(story.score, Some(12));
+    assert_eq!(story.descendants, Some(3));
+    assert_eq!(story.time.map(|time| time.as_second()), Some(1700000000));
+  }
+
+  #[test]
+  fn comment_hits_map_to_the_story_they_were_posted_in() {
+    let story = hit(json!({
+      "objectID": "43", "author": "bob", "story_id": 42, "story_title": "A story",
+      "story_url": "https://example.com", "created_at_i": 1700000000
+    }))
+    .into_story()
+    .unwrap();
+
+    assert_eq!(story.id, 42);
+    assert_eq!(story.title.as_deref(), Some("Comment by bob on: A story"));
+    assert_eq!(story.url.as_deref(), Some("https://example.com"));
+    assert_eq!(story.by.as_deref(), Some("bob"));
+  }
+
+  #[test]
+  fn hits_without_a_story_are_dropped() {
+    assert!(hit(json!({ "objectID": "44", "author": "carol" })).into_story().is_none());
+  }
+
+  // Serves `body` to a single request on a local port, returning the base URL and
+  // the request line it received.
+  async fn stand_in_server(body: serde_json::Value) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}/api/v1", listener.local_addr().unwrap());
+    let request = tokio::spawn(async move {
+      let (mut socket, _) = listener.accept().await.unwrap();
+      let mut received = Vec::new();
+      let mut buffer = [0; 1024];
+      while !received.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = socket.read(&mut buffer).await.unwrap();
+        received.extend_from_slice(&buffer[..read]);
+      }
+      let body = body.to_string();
+      let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+      );
+      socket.write_all(response.as_bytes()).await.unwrap();
+      String::from_utf8_lossy(&received).lines().next().unwrap_or_default().to_string()
+    });
+    (base_url, request)
+  }
+
+  #[tokio::test]
+  async fn searches_a_stand_in_server() {
+    let (base_url, request) = stand_in_server(json!({
+      "hits": [
+        { "objectID": "1", "title": "First" },
+        { "objectID": "2", "author": "bob", "story_id": 1, "story_title": "First" },
+        { "objectID": "3" }
+      ],
+      "page": 0,
+      "nbPages": 2
+    }))
+    .await;
+    let query = SearchQuery { text: "freya".to_string(), tag: SearchTag::Story, ..Default::default() };
+
+    let page = SearchService::with_base_url(base_url).search(&query, 0, 20).await.unwrap();
+
+    let request = request.await.unwrap();
+    assert!(request.starts_with("GET /api/v1/search?query=freya&page=0&hitsPerPage=20&tags=story "), "{request}");
+    assert_eq!(page.stories.len(), 2);
+    assert!(page.has_more);
+  }
+
+  #[tokio::test]
+  async fn searches_by_date_use_their_own_endpoint() {
+    let (base_url, request) = stand_in_server(json!({ "hits": [], "page": 1, "nbPages": 2 })).await;
+    let query = SearchQuery { sort: SearchSort::Date, ..Default::default() };
+
+    let page = SearchService::with_base_url(base_url).search(&query, 1, 20).await.unwrap();
+
+    assert!(request.await.unwrap().starts_with("GET /api/v1/search_by_date?"));
+    assert!(!page.has_more);
+  }
+
+  #[tokio::test]
+  async fn an_unreachable_server_is_a_connection_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let error = SearchService::with_base_url(base_url).search(&SearchQuery::default(), 0, 20).await.err().unwrap();
+    assert!(error.is_connection_error(), "{error:?}");
+  }
+}
@@ -0,0 +1,101 @@
+//! Small JSON files in the data directory for state kept between sessions, such as
+//! the layout. Missing or unreadable files fall back to the defaults. State that
+//! changes often is written in the background with `save_later`.
+
+use crate::utils::paths;
+use log::warn;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io::ErrorKind;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// How long a delayed write waits for further changes to the same file.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+type Serializer = Box<dyn FnOnce() -> serde_json::Result<Vec<u8>> + Send>;
+
+// The files waiting for a delayed write, with the latest value of each.
+static PENDING: Mutex<Vec<(String, Serializer)>> = Mutex::new(Vec::new());
+// Held while pending files are written, so that `flush` waits for a write in progress.
+static WRITING: Mutex<()> = Mutex::new(());
+// Wakes the thread writing the pending files.
+static WRITER: LazyLock<Mutex<Sender<()>>> = LazyLock::new(|| {
+  let (sender, receiver) = mpsc::channel::<()>();
+  thread::spawn(move || {
+    while receiver.recv().is_ok() {
+      thread::sleep(SAVE_DELAY);
+      while receiver.try_recv().is_ok() {}
+      flush();
+    }
+  });
+  Mutex::new(sender)
+});
+
+/// Reads `file_name` from the data directory, or the default value if it does not
+/// exist or cannot be parsed.
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> T {
+  let Some(path) = paths::data_dir().map(|dir| dir.join(file_name)) else { return T::default() };
+  let Ok(bytes) = fs::read(&path) else { return T::default() };
+  serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+    warn!("Ignoring unreadable {}: {}", path.display(), e);
+    T::default()
+  })
+}
+
+/// Writes `value` to `file_name` in the data directory, which may include
+/// subdirectories. The file is replaced as a whole, so an interrupted write leaves
+/// the previous contents in place.
+pub fn save<T: Serialize>(file_name: &str, value: &T) {
+  write(file_name, || serde_json::to_vec_pretty(value));
+}
+
+/// Like `save`, but serializes and writes `value` on a background thread a moment
+/// later, for state that changes often and is large enough to stall the UI. Only the
+/// last value saved to a file within the delay is written.
+pub fn save_later<T: Serialize + Send + 'static>(file_name: &str, value: T) {
+  {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    pending.retain(|(name, _)| name != file_name);
+    pending.push((file_name.to_string(), Box::new(move || serde_json::to_vec_pretty(&value))));
+  }
+  let _ = WRITER.lock().unwrap_or_else(|e| e.into_inner()).send(());
+}
+
+/// Writes the values `save_later` is still holding back, e.g. before the app exits.
+pub fn flush() {
+  let _writing = WRITING.lock().unwrap_or_else(|e| e.into_inner());
+  let pending = std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()));
+  for (file_name, serialize) in pending {
+    write(&file_name, serialize);
+  }
+}
+
+fn write(file_name: &str, serialize: impl FnOnce() -> serde_json::Result<Vec<u8>>) {
+  let Some(dir) = paths::data_dir() else { return };
+  let path = dir.join(file_name);
+  let temp_path = dir.join(format!("{file_name}.tmp"));
+  let parent = path.parent().unwrap_or(&dir);
+  let result = fs::create_dir_all(parent)
+    .and_then(|_| serialize().map_err(Into::into))
+    .and_then(|bytes| fs::write(&temp_path, bytes))
+    .and_then(|_| fs::rename(&temp_path, &path));
+  if let Err(e) = result {
+    warn!("Failed to write {}: {}", path.display(), e);
+  }
+}
+
+/// Deletes `file_name` from the data directory, if it exists, along with a write of
+/// it `save_later` is holding back.
+pub fn remove(file_name: &str) {
+  PENDING.lock().unwrap_or_else(|e| e.into_inner()).retain(|(name, _)| name != file_name);
+  let Some(path) = paths::data_dir().map(|dir| dir.join(file_name)) else { return };
+  if let Err(e) = fs::remove_file(&path)
+    && e.kind() != ErrorKind::NotFound
+  {
+    warn!("Failed to delete {}: {}", path.display(), e);
+  }
+}
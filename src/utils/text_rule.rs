@@ -0,0 +1,35 @@
+//! Rules matching text by keyword or regular expression, shared by the mutes and
+//! the highlights.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TextRule {
+  /// Matches the text literally.
+  Keyword(String),
+  /// Matches what the regular expression finds.
+  Regex(String),
+}
+
+impl TextRule {
+  /// The rule as a regular expression ignoring case. Fails only for invalid
+  /// `Regex` patterns.
+  pub fn compile(&self) -> Result<Regex, regex::Error> {
+    let pattern = match self {
+      TextRule::Keyword(keyword) => regex::escape(keyword),
+      TextRule::Regex(pattern) => pattern.clone(),
+    };
+    RegexBuilder::new(&pattern).case_insensitive(true).build()
+  }
+}
+
+impl fmt::Display for TextRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TextRule::Keyword(keyword) => write!(f, "Keyword: {keyword}"),
+      TextRule::Regex(pattern) => write!(f, "Regex: {pattern}"),
+    }
+  }
+}